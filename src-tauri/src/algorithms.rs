@@ -0,0 +1,768 @@
+use adler::Adler32;
+use gost94::Gost94Test;
+use md5::Md5;
+use ripemd::Ripemd160;
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use sha3::{Sha3_256, Sha3_512};
+use std::io::{self, Read};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use tiger::Tiger;
+use whirlpool::Whirlpool;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Size of the buffer used when streaming data through a hasher.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Bounds for a caller-chosen buffer size. Below 4 KiB the syscall count
+/// dominates; above 16 MiB memory use grows for no measurable gain.
+const MIN_BUFFER_SIZE: usize = 4 * 1024;
+const MAX_BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+/// Resolves a requested read buffer size, defaulting to [`CHUNK_SIZE`] and
+/// clamping to 4 KiB..=16 MiB.
+pub fn buffer_size(requested: Option<usize>) -> usize {
+    requested
+        .unwrap_or(CHUNK_SIZE)
+        .clamp(MIN_BUFFER_SIZE, MAX_BUFFER_SIZE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake3,
+    Sha3_256,
+    Sha3_512,
+    /// 64-bit XXH3: not cryptographic, but very fast for change detection.
+    Xxh3,
+    /// Adler-32 as used by zlib streams.
+    Adler32,
+    /// Legacy digests for verifying old archives. Opt-in: not part of
+    /// [`Algorithm::DEFAULT`].
+    Ripemd160,
+    Whirlpool,
+    /// Tiger/192 (original padding), also opt-in.
+    Tiger,
+    /// GOST R 34.11-94 with the test parameter set, matching `rhash --gost94`.
+    /// Opt-in.
+    Gost94,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 15] = [
+        Algorithm::Md5,
+        Algorithm::Sha1,
+        Algorithm::Sha224,
+        Algorithm::Sha256,
+        Algorithm::Sha384,
+        Algorithm::Sha512,
+        Algorithm::Blake3,
+        Algorithm::Sha3_256,
+        Algorithm::Sha3_512,
+        Algorithm::Xxh3,
+        Algorithm::Adler32,
+        Algorithm::Ripemd160,
+        Algorithm::Whirlpool,
+        Algorithm::Tiger,
+        Algorithm::Gost94,
+    ];
+
+    /// Computed when the caller doesn't pick algorithms. Legacy ones are
+    /// left out so they only cost time when explicitly asked for.
+    pub const DEFAULT: [Algorithm; 11] = [
+        Algorithm::Md5,
+        Algorithm::Sha1,
+        Algorithm::Sha224,
+        Algorithm::Sha256,
+        Algorithm::Sha384,
+        Algorithm::Sha512,
+        Algorithm::Blake3,
+        Algorithm::Sha3_256,
+        Algorithm::Sha3_512,
+        Algorithm::Xxh3,
+        Algorithm::Adler32,
+    ];
+
+    /// Parses an algorithm id such as `"sha256"`, ignoring case.
+    pub fn from_id(id: &str) -> Option<Algorithm> {
+        Algorithm::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.id().eq_ignore_ascii_case(id.trim()))
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "md5",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Sha224 => "sha224",
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha384 => "sha384",
+            Algorithm::Sha512 => "sha512",
+            Algorithm::Blake3 => "blake3",
+            Algorithm::Sha3_256 => "sha3_256",
+            Algorithm::Sha3_512 => "sha3_512",
+            Algorithm::Xxh3 => "xxh3",
+            Algorithm::Adler32 => "adler32",
+            Algorithm::Ripemd160 => "ripemd160",
+            Algorithm::Whirlpool => "whirlpool",
+            Algorithm::Tiger => "tiger",
+            Algorithm::Gost94 => "gost94",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "MD5",
+            Algorithm::Sha1 => "SHA-1",
+            Algorithm::Sha224 => "SHA-224",
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Sha384 => "SHA-384",
+            Algorithm::Sha512 => "SHA-512",
+            Algorithm::Blake3 => "BLAKE3",
+            Algorithm::Sha3_256 => "SHA3-256",
+            Algorithm::Sha3_512 => "SHA3-512",
+            Algorithm::Xxh3 => "XXH3",
+            Algorithm::Adler32 => "Adler-32",
+            Algorithm::Ripemd160 => "RIPEMD-160",
+            Algorithm::Whirlpool => "Whirlpool",
+            Algorithm::Tiger => "Tiger",
+            Algorithm::Gost94 => "GOST R 34.11-94",
+        }
+    }
+
+    /// Length of the lowercase hex digest.
+    pub fn hex_length(self) -> usize {
+        match self {
+            Algorithm::Adler32 => 8,
+            Algorithm::Xxh3 => 16,
+            Algorithm::Md5 => 32,
+            Algorithm::Sha1 | Algorithm::Ripemd160 => 40,
+            Algorithm::Tiger => 48,
+            Algorithm::Sha224 => 56,
+            Algorithm::Sha256 | Algorithm::Blake3 | Algorithm::Sha3_256 | Algorithm::Gost94 => 64,
+            Algorithm::Sha384 => 96,
+            Algorithm::Sha512 | Algorithm::Sha3_512 | Algorithm::Whirlpool => 128,
+        }
+    }
+
+    /// False for checksums that only catch accidental corruption. MD5 and
+    /// SHA-1 count as cryptographic even though collisions are practical.
+    pub fn is_cryptographic(self) -> bool {
+        !matches!(self, Algorithm::Xxh3 | Algorithm::Adler32)
+    }
+}
+
+/// Describes one algorithm so the UI can build its choices dynamically.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct AlgorithmInfo {
+    pub id: String,
+    pub display_name: String,
+    pub hex_length: usize,
+    pub cryptographic: bool,
+    /// Whether it's computed when no algorithms are selected.
+    pub default: bool,
+}
+
+impl From<Algorithm> for AlgorithmInfo {
+    fn from(algorithm: Algorithm) -> Self {
+        AlgorithmInfo {
+            id: algorithm.id().to_string(),
+            display_name: algorithm.display_name().to_string(),
+            hex_length: algorithm.hex_length(),
+            cryptographic: algorithm.is_cryptographic(),
+            default: Algorithm::DEFAULT.contains(&algorithm),
+        }
+    }
+}
+
+/// Lists every algorithm the backend can compute, in display order.
+#[tauri::command]
+pub fn supported_algorithms() -> Vec<AlgorithmInfo> {
+    Algorithm::ALL
+        .into_iter()
+        .map(AlgorithmInfo::from)
+        .collect()
+}
+
+/// What this machine offers for hashing, to explain speed differences.
+#[derive(serde::Serialize, Debug)]
+pub struct Capabilities {
+    pub logical_cpus: usize,
+    /// Whether the CPU has SHA instructions, which the `sha2` crate picks
+    /// up automatically at runtime.
+    pub simd_sha: bool,
+}
+
+/// Mirrors the runtime check `sha2` makes before using the SHA-NI
+/// (x86) or SHA2 (ARMv8) instructions.
+fn sha_instructions_available() -> bool {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        std::arch::is_x86_feature_detected!("sha")
+            && std::arch::is_x86_feature_detected!("sse2")
+            && std::arch::is_x86_feature_detected!("ssse3")
+            && std::arch::is_x86_feature_detected!("sse4.1")
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        std::arch::is_aarch64_feature_detected!("sha2")
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        false
+    }
+}
+
+#[tauri::command]
+pub fn hashing_capabilities() -> Capabilities {
+    Capabilities {
+        logical_cpus: thread::available_parallelism().map_or(1, |n| n.get()),
+        simd_sha: sha_instructions_available(),
+    }
+}
+
+/// Individual CPU features that the hashing crates select at runtime. All
+/// are false on architectures without them.
+#[derive(serde::Serialize, Debug)]
+pub struct HashFeatures {
+    /// SHA-NI on x86, the SHA2 crypto extension on ARMv8.
+    pub sha: bool,
+    pub avx2: bool,
+    pub sse41: bool,
+}
+
+#[tauri::command]
+pub fn cpu_hash_features() -> HashFeatures {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        HashFeatures {
+            sha: std::arch::is_x86_feature_detected!("sha"),
+            avx2: std::arch::is_x86_feature_detected!("avx2"),
+            sse41: std::arch::is_x86_feature_detected!("sse4.1"),
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        HashFeatures {
+            sha: std::arch::is_aarch64_feature_detected!("sha2"),
+            avx2: false,
+            sse41: false,
+        }
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        HashFeatures {
+            sha: false,
+            avx2: false,
+            sse41: false,
+        }
+    }
+}
+
+/// Parses a list of algorithm ids. An empty list selects
+/// [`Algorithm::DEFAULT`], and duplicates are dropped while keeping the
+/// caller's order.
+pub fn parse_selection<S: AsRef<str>>(ids: &[S]) -> Result<Vec<Algorithm>, String> {
+    if ids.is_empty() {
+        return Ok(Algorithm::DEFAULT.to_vec());
+    }
+
+    let mut selected = Vec::new();
+    for id in ids {
+        let id = id.as_ref();
+        let algorithm =
+            Algorithm::from_id(id).ok_or_else(|| format!("unsupported algorithm: {}", id))?;
+        if !selected.contains(&algorithm) {
+            selected.push(algorithm);
+        }
+    }
+    Ok(selected)
+}
+
+/// An in-progress digest for any [`Algorithm`].
+pub enum Hasher {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha224(Sha224),
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+    Sha3_256(Sha3_256),
+    Sha3_512(Sha3_512),
+    Xxh3(Box<Xxh3>),
+    Adler32(Adler32),
+    Ripemd160(Ripemd160),
+    Whirlpool(Box<Whirlpool>),
+    Tiger(Tiger),
+    Gost94(Box<Gost94Test>),
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Hasher {
+        match algorithm {
+            Algorithm::Md5 => Hasher::Md5(Md5::new()),
+            Algorithm::Sha1 => Hasher::Sha1(Sha1::new()),
+            Algorithm::Sha224 => Hasher::Sha224(Sha224::new()),
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            Algorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Sha3_256 => Hasher::Sha3_256(Sha3_256::new()),
+            Algorithm::Sha3_512 => Hasher::Sha3_512(Sha3_512::new()),
+            Algorithm::Xxh3 => Hasher::Xxh3(Box::new(Xxh3::new())),
+            Algorithm::Adler32 => Hasher::Adler32(Adler32::new()),
+            Algorithm::Ripemd160 => Hasher::Ripemd160(Ripemd160::new()),
+            Algorithm::Whirlpool => Hasher::Whirlpool(Box::new(Whirlpool::new())),
+            Algorithm::Tiger => Hasher::Tiger(Tiger::new()),
+            Algorithm::Gost94 => Hasher::Gost94(Box::new(Gost94Test::new())),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(data),
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Sha224(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha384(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Blake3(h) => {
+                h.update(data);
+            }
+            Hasher::Sha3_256(h) => h.update(data),
+            Hasher::Sha3_512(h) => h.update(data),
+            Hasher::Xxh3(h) => h.update(data),
+            Hasher::Adler32(h) => h.write_slice(data),
+            Hasher::Ripemd160(h) => h.update(data),
+            Hasher::Whirlpool(h) => h.update(data),
+            Hasher::Tiger(h) => h.update(data),
+            Hasher::Gost94(h) => h.update(data),
+        }
+    }
+
+    /// Consumes the hasher and returns the raw digest bytes.
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Md5(h) => h.finalize().to_vec(),
+            Hasher::Sha1(h) => h.finalize().to_vec(),
+            Hasher::Sha224(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Sha384(h) => h.finalize().to_vec(),
+            Hasher::Sha512(h) => h.finalize().to_vec(),
+            Hasher::Blake3(h) => h.finalize().as_bytes().to_vec(),
+            Hasher::Sha3_256(h) => h.finalize().to_vec(),
+            Hasher::Sha3_512(h) => h.finalize().to_vec(),
+            // Big-endian so the hex matches the reference `xxhsum` output
+            Hasher::Xxh3(h) => h.digest().to_be_bytes().to_vec(),
+            Hasher::Adler32(h) => h.checksum().to_be_bytes().to_vec(),
+            Hasher::Ripemd160(h) => h.finalize().to_vec(),
+            Hasher::Whirlpool(h) => h.finalize().to_vec(),
+            Hasher::Tiger(h) => h.finalize().to_vec(),
+            Hasher::Gost94(h) => h.finalize().to_vec(),
+        }
+    }
+}
+
+/// Chunks each worker may have queued before [`ParallelHashers::update`]
+/// blocks, which bounds memory use when one algorithm is slower than the
+/// reader.
+const QUEUE_DEPTH: usize = 4;
+
+/// Inputs up to this size are hashed on the calling thread. Starting a
+/// thread per algorithm costs more than hashing a small file, and a folder
+/// of them would otherwise spawn threads for every file.
+const PARALLEL_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+/// Runs one [`Hasher`] per algorithm on its own thread once the input
+/// passes [`PARALLEL_THRESHOLD`], so computing several digests of a large
+/// file costs roughly the slowest one rather than their sum. Every worker
+/// sees the same shared chunk.
+pub struct ParallelHashers {
+    stage: Stage,
+}
+
+enum Stage {
+    /// Still small enough to hash on the calling thread.
+    Inline {
+        hashers: Vec<Hasher>,
+        bytes: u64,
+    },
+    Threaded(Vec<Worker>),
+}
+
+struct Worker {
+    sender: SyncSender<Arc<[u8]>>,
+    handle: JoinHandle<Vec<u8>>,
+}
+
+impl Worker {
+    /// Moves `hasher`, with whatever it has already seen, onto a thread.
+    fn spawn(mut hasher: Hasher) -> Worker {
+        let (sender, receiver) = mpsc::sync_channel::<Arc<[u8]>>(QUEUE_DEPTH);
+        let handle = thread::spawn(move || {
+            for chunk in receiver {
+                hasher.update(&chunk);
+            }
+            hasher.finalize()
+        });
+        Worker { sender, handle }
+    }
+}
+
+impl ParallelHashers {
+    pub fn new(algorithms: &[Algorithm]) -> ParallelHashers {
+        ParallelHashers {
+            stage: Stage::Inline {
+                hashers: algorithms.iter().map(|&a| Hasher::new(a)).collect(),
+                bytes: 0,
+            },
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.stage {
+            Stage::Inline { hashers, bytes } => {
+                for hasher in hashers.iter_mut() {
+                    hasher.update(data);
+                }
+                *bytes += data.len() as u64;
+                // One algorithm gains nothing from a thread
+                if *bytes > PARALLEL_THRESHOLD && hashers.len() > 1 {
+                    let workers = hashers.drain(..).map(Worker::spawn).collect();
+                    self.stage = Stage::Threaded(workers);
+                }
+            }
+            Stage::Threaded(workers) => {
+                let chunk: Arc<[u8]> = Arc::from(data);
+                for worker in workers.iter() {
+                    // A worker that died is reported by `finalize`, so the
+                    // others carry on
+                    let _ = worker.sender.send(Arc::clone(&chunk));
+                }
+            }
+        }
+    }
+
+    /// Waits for every worker and returns the raw digests in the order the
+    /// algorithms were given. A worker that panicked yields an error in its
+    /// slot without affecting the rest.
+    pub fn finalize(self) -> Vec<Result<Vec<u8>, String>> {
+        match self.stage {
+            Stage::Inline { hashers, .. } => hashers
+                .into_iter()
+                .map(|hasher| Ok(hasher.finalize()))
+                .collect(),
+            Stage::Threaded(workers) => workers
+                .into_iter()
+                .map(|worker| {
+                    // Closing the channel lets the worker finish its digest
+                    drop(worker.sender);
+                    worker
+                        .handle
+                        .join()
+                        .map_err(|_| "hasher thread panicked".to_string())
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the digests have moved onto worker threads.
+    #[cfg(test)]
+    fn is_threaded(&self) -> bool {
+        matches!(self.stage, Stage::Threaded(_))
+    }
+}
+
+/// Hashes everything readable from `reader` with a single algorithm,
+/// returning the lowercase hex digest.
+pub fn hash_reader<R: Read>(reader: R, algorithm: Algorithm) -> io::Result<String> {
+    hash_reader_raw(reader, algorithm).map(|digest| hex_lower(&digest))
+}
+
+/// Like [`hash_reader`] but returns the raw digest bytes.
+pub fn hash_reader_raw<R: Read>(reader: R, algorithm: Algorithm) -> io::Result<Vec<u8>> {
+    let mut hasher = Hasher::new(algorithm);
+    for_each_chunk(reader, |chunk| hasher.update(chunk))?;
+    Ok(hasher.finalize())
+}
+
+/// Hashes `reader` once with several algorithms, returning the byte count
+/// and the raw digests in the same order as `algorithms`.
+pub fn hash_reader_multi_raw<R: Read>(
+    reader: R,
+    algorithms: &[Algorithm],
+) -> io::Result<(u64, Vec<Vec<u8>>)> {
+    let mut hashers: Vec<Hasher> = algorithms.iter().map(|&a| Hasher::new(a)).collect();
+    let total = for_each_chunk(reader, |chunk| {
+        for hasher in hashers.iter_mut() {
+            hasher.update(chunk);
+        }
+    })?;
+    Ok((total, hashers.into_iter().map(Hasher::finalize).collect()))
+}
+
+/// Reads `reader` to the end in [`CHUNK_SIZE`] pieces, passing each one to
+/// `f`, and returns the total number of bytes read.
+pub fn for_each_chunk<R: Read>(reader: R, mut f: impl FnMut(&[u8])) -> io::Result<u64> {
+    try_for_each_chunk(reader, |chunk| {
+        f(chunk);
+        Ok(())
+    })
+}
+
+/// Like [`for_each_chunk`], but stops early with the first error `f` returns.
+pub fn try_for_each_chunk<R: Read>(
+    reader: R,
+    f: impl FnMut(&[u8]) -> io::Result<()>,
+) -> io::Result<u64> {
+    try_for_each_chunk_sized(reader, CHUNK_SIZE, f)
+}
+
+/// Like [`try_for_each_chunk`] with a `buffer_size`-byte read buffer.
+pub fn try_for_each_chunk_sized<R: Read>(
+    mut reader: R,
+    buffer_size: usize,
+    mut f: impl FnMut(&[u8]) -> io::Result<()>,
+) -> io::Result<u64> {
+    let mut buffer = vec![0u8; buffer_size];
+    let mut total = 0u64;
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        f(&buffer[..n])?;
+        total += n as u64;
+    }
+    Ok(total)
+}
+
+pub fn hex_lower(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_from_id() {
+        assert_eq!(Algorithm::from_id("sha256"), Some(Algorithm::Sha256));
+        assert_eq!(Algorithm::from_id("MD5"), Some(Algorithm::Md5));
+        assert_eq!(Algorithm::from_id("crc32"), None);
+    }
+
+    #[test]
+    fn test_supported_algorithms() {
+        let infos = supported_algorithms();
+        let length_of = |id: &str| {
+            infos
+                .iter()
+                .find(|info| info.id == id)
+                .map(|i| i.hex_length)
+        };
+
+        assert_eq!(length_of("md5"), Some(32));
+        assert_eq!(length_of("sha1"), Some(40));
+        assert_eq!(length_of("sha256"), Some(64));
+        assert_eq!(length_of("sha512"), Some(128));
+        assert!(!infos.iter().find(|i| i.id == "xxh3").unwrap().cryptographic);
+    }
+
+    #[test]
+    fn test_hashing_capabilities() {
+        assert!(hashing_capabilities().logical_cpus >= 1);
+    }
+
+    #[test]
+    fn test_cpu_hash_features() {
+        let features = cpu_hash_features();
+        // The accelerated SHA path also needs SSE4.1 on x86
+        if cfg!(any(target_arch = "x86", target_arch = "x86_64")) && sha_instructions_available() {
+            assert!(features.sha && features.sse41);
+        }
+    }
+
+    #[test]
+    fn test_legacy_algorithms_are_opt_in() {
+        assert!(!Algorithm::DEFAULT.contains(&Algorithm::Ripemd160));
+        assert!(!Algorithm::DEFAULT.contains(&Algorithm::Whirlpool));
+        assert!(!Algorithm::DEFAULT.contains(&Algorithm::Tiger));
+        assert!(!Algorithm::DEFAULT.contains(&Algorithm::Gost94));
+        let infos = supported_algorithms();
+        assert!(!infos.iter().find(|i| i.id == "whirlpool").unwrap().default);
+        assert!(infos.iter().find(|i| i.id == "sha256").unwrap().default);
+    }
+
+    #[test]
+    fn test_ripemd160_vectors() {
+        assert_eq!(
+            hash_reader(Cursor::new(b""), Algorithm::Ripemd160).unwrap(),
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31"
+        );
+        assert_eq!(
+            hash_reader(Cursor::new(b"abc"), Algorithm::Ripemd160).unwrap(),
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+        );
+    }
+
+    #[test]
+    fn test_whirlpool_vectors() {
+        assert_eq!(
+            hash_reader(Cursor::new(b""), Algorithm::Whirlpool).unwrap(),
+            "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7\
+             3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3"
+        );
+        assert_eq!(
+            hash_reader(Cursor::new(b"abc"), Algorithm::Whirlpool).unwrap(),
+            "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c\
+             7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5"
+        );
+    }
+
+    #[test]
+    fn test_tiger_vectors() {
+        assert_eq!(
+            hash_reader(Cursor::new(b""), Algorithm::Tiger).unwrap(),
+            "3293ac630c13f0245f92bbb1766e16167a4e58492dde73f3"
+        );
+        assert_eq!(
+            hash_reader(Cursor::new(b"abc"), Algorithm::Tiger).unwrap(),
+            "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93"
+        );
+    }
+
+    #[test]
+    fn test_gost94_vectors() {
+        assert_eq!(
+            hash_reader(Cursor::new(b""), Algorithm::Gost94).unwrap(),
+            "ce85b99cc46752fffee35cab9a7b0278abb4c2d2055cff685af4912c49490f8d"
+        );
+        assert_eq!(
+            hash_reader(Cursor::new(b"abc"), Algorithm::Gost94).unwrap(),
+            "f3134348c44fb1b2a277729e2285ebb5cb5e0f29c975bc753b70497c06a4d51d"
+        );
+    }
+
+    #[test]
+    fn test_hex_length_matches_output() {
+        for algorithm in Algorithm::ALL {
+            let digest = hash_reader(Cursor::new(b"length"), algorithm).unwrap();
+            assert_eq!(digest.len(), algorithm.hex_length(), "{}", algorithm.id());
+        }
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(
+            parse_selection::<&str>(&[]).unwrap(),
+            Algorithm::DEFAULT.to_vec()
+        );
+        assert_eq!(
+            parse_selection(&["sha256", "md5", "SHA256"]).unwrap(),
+            vec![Algorithm::Sha256, Algorithm::Md5]
+        );
+        assert!(parse_selection(&["sha256", "crc32"]).is_err());
+    }
+
+    #[test]
+    fn test_hash_reader_known_content() {
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let result = hash_reader(Cursor::new(content), Algorithm::Sha256).unwrap();
+        assert_eq!(
+            result,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+    }
+
+    #[test]
+    fn test_xxh3_known_vectors() {
+        assert_eq!(
+            hash_reader(Cursor::new(b""), Algorithm::Xxh3).unwrap(),
+            "2d06800538d394c2"
+        );
+        assert_eq!(
+            hash_reader(Cursor::new(b"abc"), Algorithm::Xxh3).unwrap(),
+            "78af5f94892f3950"
+        );
+    }
+
+    #[test]
+    fn test_adler32_known_vectors() {
+        // An empty input leaves Adler-32 at its initial value of 1
+        assert_eq!(
+            hash_reader(Cursor::new(b""), Algorithm::Adler32).unwrap(),
+            "00000001"
+        );
+        assert_eq!(
+            hash_reader(Cursor::new(b"Wikipedia"), Algorithm::Adler32).unwrap(),
+            "11e60398"
+        );
+    }
+
+    #[test]
+    fn test_small_input_stays_inline() {
+        let content: Vec<u8> = (0..CHUNK_SIZE * 5).map(|i| (i % 253) as u8).collect();
+        let mut hashers = ParallelHashers::new(&Algorithm::ALL);
+        for chunk in content.chunks(CHUNK_SIZE) {
+            hashers.update(chunk);
+        }
+        assert!(!hashers.is_threaded());
+
+        let (_, sequential) =
+            hash_reader_multi_raw(Cursor::new(&content), &Algorithm::ALL).unwrap();
+        let inline: Vec<Vec<u8>> = hashers.finalize().into_iter().map(Result::unwrap).collect();
+        assert_eq!(inline, sequential);
+    }
+
+    #[test]
+    fn test_parallel_hashers_match_sequential() {
+        // Large enough to switch to threads part-way through
+        let len = PARALLEL_THRESHOLD as usize + CHUNK_SIZE * 3;
+        let content: Vec<u8> = (0..len).map(|i| (i % 253) as u8).collect();
+        let mut parallel = ParallelHashers::new(&Algorithm::ALL);
+        for chunk in content.chunks(CHUNK_SIZE) {
+            parallel.update(chunk);
+        }
+        assert!(parallel.is_threaded());
+
+        let (_, sequential) =
+            hash_reader_multi_raw(Cursor::new(&content), &Algorithm::ALL).unwrap();
+        let parallel: Vec<Vec<u8>> = parallel
+            .finalize()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn test_buffer_size_clamped() {
+        assert_eq!(buffer_size(None), CHUNK_SIZE);
+        assert_eq!(buffer_size(Some(1)), 4 * 1024);
+        assert_eq!(buffer_size(Some(1 << 30)), 16 * 1024 * 1024);
+        assert_eq!(buffer_size(Some(1024 * 1024)), 1024 * 1024);
+    }
+
+    #[test]
+    fn test_hash_reader_spans_chunks() {
+        // Larger than one chunk so the read loop runs several times
+        let content = vec![0xAB; CHUNK_SIZE * 3 + 17];
+        let streamed = hash_reader(Cursor::new(&content), Algorithm::Md5).unwrap();
+
+        let mut hasher = Md5::new();
+        hasher.update(&content);
+        assert_eq!(streamed, format!("{:x}", hasher.finalize()));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::algorithms::{self, Algorithm};
+use crate::{hash_content, HashResult};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::Read;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+/// SHA-256 of one file inside an archive.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct ArchiveEntryHash {
+    pub entry_name: String,
+    pub uncompressed_size: u64,
+    pub sha256: String,
+}
+
+fn sha256_entry<R: Read>(
+    archive_path: &str,
+    entry_name: String,
+    uncompressed_size: u64,
+    reader: R,
+) -> Result<ArchiveEntryHash, String> {
+    let sha256 = algorithms::hash_reader(reader, Algorithm::Sha256)
+        .map_err(|e| format!("{}: {}: {}", archive_path, entry_name, e))?;
+    Ok(ArchiveEntryHash {
+        entry_name,
+        uncompressed_size,
+        sha256,
+    })
+}
+
+fn open_zip(zip_path: &str) -> Result<ZipArchive<File>, String> {
+    let file = File::open(zip_path).map_err(|e| format!("{}: {}", zip_path, e))?;
+    ZipArchive::new(file).map_err(|e| format!("{}: not a readable zip archive: {}", zip_path, e))
+}
+
+fn zip_entry_hash(zip_path: &str, entry_name: &str) -> Result<HashResult, String> {
+    let mut archive = open_zip(zip_path)?;
+    let entry = match archive.by_name(entry_name) {
+        Ok(entry) => entry,
+        Err(ZipError::FileNotFound) => {
+            return Err(format!("{}: no entry named {}", zip_path, entry_name));
+        }
+        Err(e) => return Err(format!("{}: {}: {}", zip_path, entry_name, e)),
+    };
+    if entry.is_dir() {
+        return Err(format!("{}: {} is a directory", zip_path, entry_name));
+    }
+    let size = entry.size();
+    let mut result = hash_content(entry, size, &Algorithm::DEFAULT, None, |_| {}, None)
+        .map_err(|e| format!("{}: {}: {}", zip_path, entry_name, e))?;
+    result.set_path(entry_name);
+    Ok(result)
+}
+
+fn zip_listing(path: &str) -> Result<Vec<ArchiveEntryHash>, String> {
+    let mut archive = open_zip(path)?;
+    let mut hashes = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| format!("{}: entry {}: {}", path, index, e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let (name, size) = (entry.name().to_string(), entry.size());
+        hashes.push(sha256_entry(path, name, size, entry)?);
+    }
+    Ok(hashes)
+}
+
+fn tar_listing<R: Read>(path: &str, reader: R) -> Result<Vec<ArchiveEntryHash>, String> {
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("{}: not a readable tar archive: {}", path, e))?;
+    let mut hashes = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("{}: {}", path, e))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|e| format!("{}: {}", path, e))?
+            .to_string_lossy()
+            .to_string();
+        let size = entry.size();
+        hashes.push(sha256_entry(path, name, size, entry)?);
+    }
+    Ok(hashes)
+}
+
+fn archive_listing(path: &str) -> Result<Vec<ArchiveEntryHash>, String> {
+    let lower = path.to_ascii_lowercase();
+    if lower.ends_with(".zip") {
+        return zip_listing(path);
+    }
+    let open = || File::open(path).map_err(|e| format!("{}: {}", path, e));
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        tar_listing(path, GzDecoder::new(open()?))
+    } else if lower.ends_with(".tar") {
+        tar_listing(path, open()?)
+    } else {
+        Err(format!(
+            "{}: unsupported archive type (expected .zip, .tar, .tar.gz or .tgz)",
+            path
+        ))
+    }
+}
+
+/// Hashes every file in a zip or tar archive, picked by extension
+/// (`.zip`, `.tar`, `.tar.gz`, `.tgz`), in archive order. Directories and
+/// links are skipped, so the list can serve as a manifest of the contents.
+#[tauri::command]
+pub async fn hash_archive(path: String) -> Result<Vec<ArchiveEntryHash>, String> {
+    archive_listing(&path)
+}
+
+/// Hashes one file inside a zip without extracting it, streaming the
+/// decompressed bytes. The result's `path` is the entry name and the
+/// timestamps are left empty.
+#[tauri::command]
+pub async fn hash_zip_entry(zip_path: String, entry_name: String) -> Result<HashResult, String> {
+    zip_entry_hash(&zip_path, &entry_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{Cursor, Write};
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    fn create_zip(dir: &TempDir) -> String {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        writer.add_directory("docs/", deflated).unwrap();
+        writer.start_file("docs/fox.txt", deflated).unwrap();
+        writer
+            .write_all(b"The quick brown fox jumps over the lazy dog")
+            .unwrap();
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        writer.start_file("empty.bin", stored).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let path = dir.path().join("bundle.zip");
+        fs::write(&path, bytes).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_hash_zip_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let zip_path = create_zip(&temp_dir);
+
+        let result = hash_zip_entry(zip_path.clone(), "docs/fox.txt".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            result.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(result.file_size, 43);
+        assert_eq!(result.file_name, "fox.txt");
+
+        let empty = hash_zip_entry(zip_path, "empty.bin".into()).await.unwrap();
+        assert_eq!(empty.md5, "d41d8cd98f00b204e9800998ecf8427e");
+    }
+
+    #[tokio::test]
+    async fn test_hash_archive_zip() {
+        let temp_dir = TempDir::new().unwrap();
+        let zip_path = create_zip(&temp_dir);
+
+        let entries = hash_archive(zip_path).await.unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.entry_name.as_str()).collect();
+        assert_eq!(names, vec!["docs/fox.txt", "empty.bin"]);
+        assert_eq!(
+            entries[0],
+            ArchiveEntryHash {
+                entry_name: "docs/fox.txt".to_string(),
+                uncompressed_size: 43,
+                sha256: "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+                    .to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_archive_tar_gz() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("bundle.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        builder
+            .append_data(&mut header, "docs/fox.txt", &content[..])
+            .unwrap();
+        let mut dir_header = tar::Header::new_gnu();
+        dir_header.set_entry_type(tar::EntryType::Directory);
+        dir_header.set_size(0);
+        builder
+            .append_data(&mut dir_header, "docs/", &[][..])
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let entries = hash_archive(path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].entry_name, "docs/fox.txt");
+        assert_eq!(
+            entries[0].sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+
+        assert!(hash_archive("bundle.rar".into()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hash_zip_entry_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let zip_path = create_zip(&temp_dir);
+
+        let missing = hash_zip_entry(zip_path.clone(), "nope.txt".into()).await;
+        assert!(missing.unwrap_err().contains("no entry named nope.txt"));
+        assert!(hash_zip_entry(zip_path, "docs/".into()).await.is_err());
+
+        let corrupt = temp_dir.path().join("corrupt.zip");
+        fs::write(&corrupt, b"PK\x03\x04 definitely not a zip").unwrap();
+        let error = hash_zip_entry(corrupt.to_string_lossy().to_string(), "a".into())
+            .await
+            .unwrap_err();
+        assert!(error.contains("not a readable zip archive"));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::algorithms::Algorithm;
+use std::path::Path;
+use std::time::Duration;
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+/// Target of the audit events, so nothing else that logs through `tracing`
+/// ends up in the file.
+const AUDIT_TARGET: &str = "audit";
+
+/// Log files are named `audit.log.YYYY-MM-DD`.
+const LOG_FILE_PREFIX: &str = "audit.log";
+
+/// Keeps the background writer running; buffered lines are flushed when
+/// this is dropped.
+pub struct AuditLog(#[allow(dead_code)] WorkerGuard);
+
+/// Builds a subscriber writing audit events as JSON lines to a daily file
+/// in `log_dir`. Lines go through a channel to a writer thread, so hashing
+/// never waits on the disk.
+fn subscriber(log_dir: &Path) -> (impl tracing::Subscriber + Send + Sync, WorkerGuard) {
+    let appender = tracing_appender::rolling::daily(log_dir, LOG_FILE_PREFIX);
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let layer = tracing_subscriber::fmt::layer()
+        .json()
+        .with_writer(writer)
+        .with_filter(Targets::new().with_target(AUDIT_TARGET, Level::INFO));
+    (tracing_subscriber::registry().with(layer), guard)
+}
+
+/// Starts writing the audit log to `log_dir` for the rest of the process.
+pub fn init(log_dir: &Path) -> Result<AuditLog, String> {
+    let (subscriber, guard) = subscriber(log_dir);
+    tracing::subscriber::set_global_default(subscriber).map_err(|e| e.to_string())?;
+    Ok(AuditLog(guard))
+}
+
+/// Records one hash operation. Does nothing unless [`init`] was called.
+pub fn record_hash(
+    path: &str,
+    algorithms: &[Algorithm],
+    elapsed: Duration,
+    error: Option<&dyn std::fmt::Display>,
+) {
+    let ids: Vec<&str> = algorithms.iter().map(|algorithm| algorithm.id()).collect();
+    let algorithms = ids.join(",");
+    let duration_ms = elapsed.as_millis() as u64;
+    match error {
+        None => tracing::info!(
+            target: AUDIT_TARGET,
+            path,
+            algorithms,
+            duration_ms,
+            success = true,
+            "hash"
+        ),
+        Some(error) => tracing::info!(
+            target: AUDIT_TARGET,
+            path,
+            algorithms,
+            duration_ms,
+            success = false,
+            error = %error,
+            "hash"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn log_lines(log_dir: &Path) -> Vec<serde_json::Value> {
+        let files: Vec<_> = fs::read_dir(log_dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        let name = files[0].file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with(LOG_FILE_PREFIX), "{}", name);
+        fs::read_to_string(&files[0])
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_successful_hash_is_logged() {
+        let temp_dir = TempDir::new().unwrap();
+        let (subscriber, guard) = subscriber(temp_dir.path());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("not an audit event");
+            record_hash(
+                "/downloads/fox.txt",
+                &[Algorithm::Md5, Algorithm::Sha256],
+                Duration::from_millis(12),
+                None,
+            );
+        });
+        drop(guard);
+
+        let lines = log_lines(temp_dir.path());
+        assert_eq!(lines.len(), 1);
+        let fields = &lines[0]["fields"];
+        assert_eq!(fields["path"], "/downloads/fox.txt");
+        assert_eq!(fields["algorithms"], "md5,sha256");
+        assert_eq!(fields["duration_ms"], 12);
+        assert_eq!(fields["success"], true);
+    }
+
+    #[test]
+    fn test_failed_hash_is_logged() {
+        let temp_dir = TempDir::new().unwrap();
+        let (subscriber, guard) = subscriber(temp_dir.path());
+        tracing::subscriber::with_default(subscriber, || {
+            record_hash(
+                "missing.bin",
+                &[Algorithm::Sha256],
+                Duration::ZERO,
+                Some(&"No such file or directory"),
+            );
+        });
+        drop(guard);
+
+        let fields = &log_lines(temp_dir.path())[0]["fields"];
+        assert_eq!(fields["success"], false);
+        assert_eq!(fields["error"], "No such file or directory");
+    }
+}
@@ -0,0 +1,102 @@
+use crate::algorithms;
+use blake2::digest::{Update, VariableOutput};
+use blake2::{Blake2bVar, Blake2sVar};
+use std::fs::File;
+use std::io::Read;
+
+/// Digest sizes allowed by RFC 7693: up to 64 bytes for BLAKE2b and 32 for
+/// BLAKE2s.
+const BLAKE2B_MAX_BYTES: usize = 64;
+const BLAKE2S_MAX_BYTES: usize = 32;
+
+fn hash_variable<H: Update + VariableOutput, R: Read>(
+    reader: R,
+    mut hasher: H,
+) -> Result<String, String> {
+    algorithms::for_each_chunk(reader, |chunk| hasher.update(chunk)).map_err(|e| e.to_string())?;
+    let mut digest = vec![0u8; hasher.output_size()];
+    hasher
+        .finalize_variable(&mut digest)
+        .map_err(|e| e.to_string())?;
+    Ok(algorithms::hex_lower(&digest))
+}
+
+/// BLAKE2b or BLAKE2s (`variant` "b" or "s") of everything readable from
+/// `reader`, as hex. `output_bytes` is clamped to 1..=64 for BLAKE2b and
+/// 1..=32 for BLAKE2s. The digest length is part of the parameter block,
+/// so a shorter output is not a prefix of the full-length digest.
+fn blake2_reader<R: Read>(reader: R, variant: &str, output_bytes: usize) -> Result<String, String> {
+    let invalid = |e| format!("invalid BLAKE2 output length: {}", e);
+    match variant.trim().to_ascii_lowercase().as_str() {
+        "b" | "blake2b" => {
+            let length = output_bytes.clamp(1, BLAKE2B_MAX_BYTES);
+            hash_variable(reader, Blake2bVar::new(length).map_err(invalid)?)
+        }
+        "s" | "blake2s" => {
+            let length = output_bytes.clamp(1, BLAKE2S_MAX_BYTES);
+            hash_variable(reader, Blake2sVar::new(length).map_err(invalid)?)
+        }
+        other => Err(format!(
+            "unsupported BLAKE2 variant: {} (expected b or s)",
+            other
+        )),
+    }
+}
+
+/// Returns the BLAKE2b or BLAKE2s digest of the file with `output_bytes`
+/// bytes of output, as lowercase hex.
+#[tauri::command]
+pub async fn calculate_blake2(
+    path: String,
+    variant: String,
+    output_bytes: usize,
+) -> Result<String, String> {
+    let file = File::open(&path).map_err(|e| format!("{}: {}", path, e))?;
+    blake2_reader(file, &variant, output_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blake2b_512_abc() {
+        // RFC 7693 appendix A
+        assert_eq!(
+            blake2_reader(&b"abc"[..], "b", 64).unwrap(),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+
+    #[test]
+    fn test_blake2s_256_abc() {
+        // RFC 7693 appendix B
+        assert_eq!(
+            blake2_reader(&b"abc"[..], "s", 32).unwrap(),
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+        );
+    }
+
+    #[test]
+    fn test_blake2_truncated_output() {
+        let short = blake2_reader(&b"abc"[..], "b", 16).unwrap();
+        assert_eq!(short, "cf4ab791c62b8d2b2109c90275287816");
+        let full = blake2_reader(&b"abc"[..], "b", 64).unwrap();
+        assert!(!full.starts_with(&short));
+    }
+
+    #[test]
+    fn test_blake2_output_clamped() {
+        assert_eq!(blake2_reader(&b""[..], "s", 1000).unwrap().len(), 64);
+        assert_eq!(blake2_reader(&b""[..], "b", 0).unwrap().len(), 2);
+        assert!(blake2_reader(&b""[..], "x", 32).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calculate_blake2_missing_file() {
+        assert!(calculate_blake2("/nonexistent/file".into(), "b".into(), 64)
+            .await
+            .is_err());
+    }
+}
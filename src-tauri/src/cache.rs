@@ -0,0 +1,147 @@
+use crate::algorithms::Algorithm;
+use crate::HashResult;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Entries kept before the cache is emptied and starts over. Repeat hashes
+/// are nearly always of a handful of files, so this rarely matters.
+const CAPACITY: usize = 256;
+
+/// Size and modification time of a file when it was hashed. A change to
+/// either invalidates the cached result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileStamp {
+    size: u64,
+    modified: SystemTime,
+}
+
+impl FileStamp {
+    /// `None` when the file can't be stat'ed or the platform has no mtime,
+    /// in which case the result isn't cached.
+    pub fn of(path: &str) -> Option<FileStamp> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(FileStamp {
+            size: metadata.len(),
+            modified: metadata.modified().ok()?,
+        })
+    }
+}
+
+/// A path and the algorithms it was hashed with.
+type CacheKey = (String, Vec<Algorithm>);
+
+/// Results of `calculate_checksum` keyed by path and algorithm selection,
+/// so hashing an unchanged file again returns immediately.
+#[derive(Default)]
+pub struct HashCache {
+    entries: Mutex<HashMap<CacheKey, (FileStamp, HashResult)>>,
+    hits: AtomicU64,
+}
+
+impl HashCache {
+    /// The cached result for `path` with `algorithms`, if the file still
+    /// has the `stamp` it was hashed with.
+    pub fn get(
+        &self,
+        path: &str,
+        algorithms: &[Algorithm],
+        stamp: FileStamp,
+    ) -> Option<HashResult> {
+        let entries = self.entries.lock().unwrap();
+        let (cached_stamp, result) = entries.get(&(path.to_string(), algorithms.to_vec()))?;
+        if *cached_stamp != stamp {
+            return None;
+        }
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(result.clone())
+    }
+
+    /// Remembers `result`. `stamp` should be taken before hashing started,
+    /// so a file modified mid-hash doesn't get the stale result cached
+    /// against its new stamp.
+    pub fn insert(
+        &self,
+        path: &str,
+        algorithms: &[Algorithm],
+        stamp: FileStamp,
+        result: HashResult,
+    ) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= CAPACITY {
+            entries.clear();
+        }
+        entries.insert((path.to_string(), algorithms.to_vec()), (stamp, result));
+    }
+
+    /// Number of lookups answered from the cache.
+    #[allow(dead_code)]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn result(sha256: &str) -> HashResult {
+        HashResult {
+            sha256: sha256.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cache_hit_requires_same_stamp_and_algorithms() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("a.bin");
+        fs::write(&path, b"abc").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let cache = HashCache::default();
+        let stamp = FileStamp::of(&path).unwrap();
+        cache.insert(&path, &[Algorithm::Sha256], stamp, result("cached"));
+
+        assert_eq!(
+            cache.get(&path, &[Algorithm::Sha256], stamp),
+            Some(result("cached"))
+        );
+        assert_eq!(cache.get(&path, &[Algorithm::Md5], stamp), None);
+        assert_eq!(cache.hits(), 1);
+
+        let touched = FileStamp {
+            modified: stamp.modified + Duration::from_secs(1),
+            ..stamp
+        };
+        assert_eq!(cache.get(&path, &[Algorithm::Sha256], touched), None);
+        let resized = FileStamp { size: 4, ..stamp };
+        assert_eq!(cache.get(&path, &[Algorithm::Sha256], resized), None);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_cache_empties_when_full() {
+        let cache = HashCache::default();
+        let stamp = FileStamp {
+            size: 0,
+            modified: SystemTime::UNIX_EPOCH,
+        };
+        for i in 0..CAPACITY {
+            cache.insert(&i.to_string(), &[], stamp, result(""));
+        }
+        cache.insert("last", &[], stamp, result(""));
+
+        assert!(cache.get("0", &[], stamp).is_none());
+        assert!(cache.get("last", &[], stamp).is_some());
+    }
+
+    #[test]
+    fn test_missing_file_has_no_stamp() {
+        assert_eq!(FileStamp::of("/nonexistent/file"), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::algorithms::{self, Algorithm};
+use fastcdc::v2020::{StreamCDC, AVERAGE_MAX, AVERAGE_MIN};
+use std::fs::File;
+
+/// One content-defined chunk of a file.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct ChunkInfo {
+    pub offset: u64,
+    pub length: u64,
+    pub sha256: String,
+}
+
+/// Splits the file with FastCDC, bounding chunks to a quarter and four
+/// times `avg_chunk_bytes`.
+fn chunk_reader(file: File, avg_chunk_bytes: u32) -> Result<Vec<ChunkInfo>, String> {
+    let chunker = StreamCDC::new(
+        file,
+        avg_chunk_bytes / 4,
+        avg_chunk_bytes,
+        avg_chunk_bytes * 4,
+    );
+    chunker
+        .map(|chunk| {
+            let chunk = chunk.map_err(|e| e.to_string())?;
+            let sha256 = algorithms::hash_reader(chunk.data.as_slice(), Algorithm::Sha256)
+                .map_err(|e| e.to_string())?;
+            Ok(ChunkInfo {
+                offset: chunk.offset,
+                length: chunk.length as u64,
+                sha256,
+            })
+        })
+        .collect()
+}
+
+/// Lists content-defined chunk boundaries and their SHA-256s, for delta
+/// sync and dedup. Boundaries follow the content, so inserting bytes only
+/// changes the chunks around the edit. `avg_chunk_bytes` must be between
+/// 256 B and 4 MiB.
+#[tauri::command]
+pub async fn chunk_file(path: String, avg_chunk_bytes: u64) -> Result<Vec<ChunkInfo>, String> {
+    let avg = u32::try_from(avg_chunk_bytes)
+        .ok()
+        .filter(|avg| (AVERAGE_MIN..=AVERAGE_MAX).contains(avg))
+        .ok_or_else(|| {
+            format!(
+                "avg_chunk_bytes must be between {} and {}",
+                AVERAGE_MIN, AVERAGE_MAX
+            )
+        })?;
+    let file = File::open(&path).map_err(|e| format!("{}: {}", path, e))?;
+    chunk_reader(file, avg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// Deterministic pseudo-random bytes, so boundaries aren't trivially
+    /// periodic.
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn create_test_file(content: &[u8]) -> (NamedTempFile, String) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        (file, path)
+    }
+
+    #[tokio::test]
+    async fn test_chunks_cover_file() {
+        let content = noise(200_000);
+        let (_file, path) = create_test_file(&content);
+
+        let chunks = chunk_file(path, 4096).await.unwrap();
+        assert!(chunks.len() > 1);
+        let mut expected_offset = 0;
+        for chunk in &chunks {
+            assert_eq!(chunk.offset, expected_offset);
+            let range = chunk.offset as usize..(chunk.offset + chunk.length) as usize;
+            assert_eq!(
+                chunk.sha256,
+                algorithms::hash_reader(&content[range], Algorithm::Sha256).unwrap()
+            );
+            expected_offset += chunk.length;
+        }
+        assert_eq!(expected_offset, 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_chunks_are_deterministic() {
+        let content = noise(100_000);
+        let (_a, path_a) = create_test_file(&content);
+        let (_b, path_b) = create_test_file(&content);
+
+        assert_eq!(
+            chunk_file(path_a, 1024).await.unwrap(),
+            chunk_file(path_b, 1024).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_average() {
+        let (_file, path) = create_test_file(b"data");
+        assert!(chunk_file(path.clone(), 16).await.is_err());
+        assert!(chunk_file(path.clone(), u64::MAX).await.is_err());
+        assert!(chunk_file(path, 256).await.unwrap().len() == 1);
+    }
+}
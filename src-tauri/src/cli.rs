@@ -0,0 +1,199 @@
+use crate::algorithms::Algorithm;
+use crate::calculate_file_hash;
+
+const USAGE: &str = "usage: checksum-check [--json] [--algo <algorithm>] <file|->";
+
+/// Arguments for a headless invocation such as `checksum-check --algo sha256 -`
+/// or `checksum-check --json file.iso`.
+#[derive(Debug, PartialEq)]
+pub struct CliArgs {
+    /// Required unless `json` is set, where `None` means the
+    /// default algorithms.
+    pub algorithm: Option<Algorithm>,
+    /// Print the full result as JSON instead of a bare digest.
+    pub json: bool,
+    /// File to hash, or `-` to read from stdin.
+    pub input: String,
+}
+
+/// Parses the process arguments (without the program name).
+///
+/// Returns `None` when no CLI flags were given, meaning the GUI should start.
+pub fn parse_args(args: &[String]) -> Option<Result<CliArgs, String>> {
+    if !args.iter().any(|arg| arg == "--algo" || arg == "--json") {
+        return None;
+    }
+
+    let mut algorithm = None;
+    let mut json = false;
+    let mut input = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--algo" => {
+                let Some(id) = iter.next() else {
+                    return Some(Err("--algo requires a value".to_string()));
+                };
+                match Algorithm::from_id(id) {
+                    Some(parsed) => algorithm = Some(parsed),
+                    None => return Some(Err(format!("unsupported algorithm: {}", id))),
+                }
+            }
+            "--json" => json = true,
+            other if input.is_none() => input = Some(other.to_string()),
+            other => return Some(Err(format!("unexpected argument: {}", other))),
+        }
+    }
+
+    Some(match input {
+        Some(input) if json || algorithm.is_some() => Ok(CliArgs {
+            algorithm,
+            json,
+            input,
+        }),
+        _ => Err(USAGE.to_string()),
+    })
+}
+
+/// Release builds on Windows use the GUI subsystem and start without a
+/// console, so output would go nowhere. Reattaches to the console of the
+/// shell that launched us; when there is none (started from Explorer) this
+/// fails quietly and output is dropped as before.
+#[cfg(windows)]
+pub fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // Safety: takes no pointers; failure only means no console to attach to
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Other platforms always keep the launching terminal's stdio.
+#[cfg(not(windows))]
+pub fn attach_parent_console() {}
+
+/// Runs a headless hash and returns the process exit code.
+pub fn run(args: &CliArgs) -> i32 {
+    match hash_input(args) {
+        Ok(digest) => {
+            println!("{}", digest);
+            0
+        }
+        Err(e) => {
+            eprintln!("checksum-check: {}", e);
+            1
+        }
+    }
+}
+
+/// Hashes through the same path as the GUI, so stdin, directories and
+/// special files such as `/dev/zero` are handled the same way.
+fn hash_input(args: &CliArgs) -> Result<String, String> {
+    if args.json {
+        return json_result(args);
+    }
+    let algorithm = args.algorithm.ok_or_else(|| USAGE.to_string())?;
+    let mut result = calculate_file_hash(&args.input, &[algorithm]).map_err(|e| e.to_string())?;
+    if let Some(error) = result.errors.remove(algorithm.id()) {
+        return Err(error);
+    }
+    Ok(std::mem::take(result.digest_mut(algorithm)))
+}
+
+/// The same `HashResult` the GUI gets, as one line of JSON.
+fn json_result(args: &CliArgs) -> Result<String, String> {
+    let selected = match args.algorithm {
+        Some(algorithm) => vec![algorithm],
+        None => Algorithm::DEFAULT.to_vec(),
+    };
+    let result = calculate_file_hash(&args.input, &selected).map_err(|e| e.to_string())?;
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_without_flags_starts_gui() {
+        assert_eq!(parse_args(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_stdin() {
+        let parsed = parse_args(&args(&["--algo", "sha256", "-"])).unwrap();
+        assert_eq!(
+            parsed,
+            Ok(CliArgs {
+                algorithm: Some(Algorithm::Sha256),
+                json: false,
+                input: "-".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let parsed = parse_args(&args(&["--json", "file.iso"])).unwrap();
+        assert_eq!(
+            parsed,
+            Ok(CliArgs {
+                algorithm: None,
+                json: true,
+                input: "file.iso".to_string(),
+            })
+        );
+
+        let parsed = parse_args(&args(&["file.iso", "--json", "--algo", "md5"])).unwrap();
+        assert_eq!(parsed.unwrap().algorithm, Some(Algorithm::Md5));
+        assert!(parse_args(&args(&["--json"])).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_hash_input_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("fox.txt");
+        std::fs::write(&path, b"The quick brown fox jumps over the lazy dog").unwrap();
+        let path = path.to_string_lossy().to_string();
+        let parsed = parse_args(&args(&["--algo", "md5", path.as_str()]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            hash_input(&parsed),
+            Ok("9e107d9d372bb6826bd81d3542a419d6".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hash_input_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().to_string_lossy().to_string();
+        let parsed = parse_args(&args(&["--algo", "sha256", dir.as_str()]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(hash_input(&parsed), Err("path is a directory".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hash_input_refuses_char_device() {
+        // Would stream forever if it were read
+        let parsed = parse_args(&args(&["--algo", "sha256", "/dev/zero"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(hash_input(&parsed), Err("not a regular file".to_string()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&args(&["--algo"])).unwrap().is_err());
+        assert!(parse_args(&args(&["--algo", "nope", "-"]))
+            .unwrap()
+            .is_err());
+        assert!(parse_args(&args(&["--algo", "md5"])).unwrap().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{calculate_text_hash, HashResult};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Hashes clipboard text, treating a read failure (nothing copied, or an
+/// image) the same as an empty clipboard.
+fn hash_clipboard_content(text: Option<String>) -> Result<HashResult, String> {
+    match text {
+        Some(text) if !text.is_empty() => calculate_text_hash(&text),
+        _ => Err("clipboard is empty or does not contain text".to_string()),
+    }
+}
+
+/// Hashes whatever text is on the clipboard, as [`crate::calculate_checksum_text`]
+/// would.
+#[tauri::command]
+pub async fn hash_clipboard_text<R: Runtime>(app: AppHandle<R>) -> Result<HashResult, String> {
+    hash_clipboard_content(app.clipboard().read_text().ok())
+}
+
+/// Puts `hash` on the system clipboard. Done on the Rust side because the
+/// JS clipboard API is blocked in some webview sandboxes.
+#[tauri::command]
+pub async fn copy_hash_to_clipboard<R: Runtime>(
+    app: AppHandle<R>,
+    hash: String,
+) -> Result<(), String> {
+    app.clipboard()
+        .write_text(hash)
+        .map_err(|e| format!("cannot write to clipboard: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_clipboard_errors() {
+        assert!(hash_clipboard_content(None).is_err());
+        assert!(hash_clipboard_content(Some(String::new())).is_err());
+    }
+
+    #[test]
+    fn test_clipboard_text_is_hashed() {
+        let result =
+            hash_clipboard_content(Some("The quick brown fox jumps over the lazy dog".into()))
+                .unwrap();
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(result.file_size, 43);
+    }
+}
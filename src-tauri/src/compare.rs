@@ -0,0 +1,210 @@
+use crate::algorithms::{self, Algorithm};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+
+#[derive(serde::Serialize, Debug)]
+pub struct FileComparison {
+    pub identical: bool,
+    pub size_match: bool,
+    /// Empty when the sizes differ, since the files weren't hashed.
+    pub sha256_a: String,
+    pub sha256_b: String,
+}
+
+fn sha256_of(path: &str) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+    algorithms::hash_reader(file, Algorithm::Sha256).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Reports whether two files have identical contents. Files of different
+/// sizes are reported as different without reading them.
+#[tauri::command]
+pub async fn compare_files(path_a: String, path_b: String) -> Result<FileComparison, String> {
+    let size_a = fs::metadata(&path_a)
+        .map_err(|e| format!("{}: {}", path_a, e))?
+        .len();
+    let size_b = fs::metadata(&path_b)
+        .map_err(|e| format!("{}: {}", path_b, e))?
+        .len();
+
+    if size_a != size_b {
+        return Ok(FileComparison {
+            identical: false,
+            size_match: false,
+            sha256_a: String::new(),
+            sha256_b: String::new(),
+        });
+    }
+
+    let sha256_a = sha256_of(&path_a)?;
+    let sha256_b = sha256_of(&path_b)?;
+    Ok(FileComparison {
+        identical: sha256_a == sha256_b,
+        size_match: true,
+        sha256_a,
+        sha256_b,
+    })
+}
+
+/// Result of [`find_duplicates`].
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct Duplicates {
+    pub groups: Vec<Vec<String>>,
+    /// Number of zero-byte files, only set when they were counted instead
+    /// of grouped.
+    pub empty_files: Option<usize>,
+}
+
+/// Groups `paths` whose contents are identical, keeping input order within
+/// each group. Files are bucketed by size first, so a file whose size no
+/// other file shares is never read.
+///
+/// With `count_empty`, zero-byte files are left out of the groups and
+/// counted instead: every empty file "matches" every other, which says
+/// nothing useful and usually means truncated downloads.
+fn duplicate_groups(paths: Vec<String>, count_empty: bool) -> Result<Duplicates, String> {
+    let mut by_size: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+    for path in paths {
+        let size = fs::metadata(&path)
+            .map_err(|e| format!("{}: {}", path, e))?
+            .len();
+        by_size.entry(size).or_default().push(path);
+    }
+    let empty_files = count_empty.then(|| by_size.remove(&0).map_or(0, |paths| paths.len()));
+
+    let mut groups = Vec::new();
+    for candidates in by_size.into_values().filter(|paths| paths.len() > 1) {
+        let mut by_hash: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for path in candidates {
+            by_hash.entry(sha256_of(&path)?).or_default().push(path);
+        }
+        groups.extend(by_hash.into_values().filter(|paths| paths.len() > 1));
+    }
+    Ok(Duplicates {
+        groups,
+        empty_files,
+    })
+}
+
+/// Returns groups of paths with identical contents (by SHA-256). Files
+/// with no duplicate are left out. `count_empty` reports zero-byte files
+/// as a count rather than one big group.
+#[tauri::command]
+pub async fn find_duplicates(
+    paths: Vec<String>,
+    count_empty: Option<bool>,
+) -> Result<Duplicates, String> {
+    duplicate_groups(paths, count_empty.unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_file(content: &[u8]) -> (NamedTempFile, String) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        (file, path)
+    }
+
+    #[tokio::test]
+    async fn test_identical_files() {
+        let (_a, path_a) = create_test_file(b"same content");
+        let (_b, path_b) = create_test_file(b"same content");
+        let result = compare_files(path_a, path_b).await.unwrap();
+
+        assert!(result.identical);
+        assert!(result.size_match);
+        assert_eq!(result.sha256_a, result.sha256_b);
+        assert_eq!(result.sha256_a.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_same_size_different_content() {
+        let (_a, path_a) = create_test_file(b"Content A");
+        let (_b, path_b) = create_test_file(b"Content B");
+        let result = compare_files(path_a, path_b).await.unwrap();
+
+        assert!(!result.identical);
+        assert!(result.size_match);
+        assert_ne!(result.sha256_a, result.sha256_b);
+    }
+
+    #[tokio::test]
+    async fn test_different_size_skips_hashing() {
+        let (_a, path_a) = create_test_file(b"short");
+        let (_b, path_b) = create_test_file(b"a bit longer");
+        let result = compare_files(path_a, path_b).await.unwrap();
+
+        assert!(!result.identical);
+        assert!(!result.size_match);
+        assert!(result.sha256_a.is_empty());
+        assert!(result.sha256_b.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates() {
+        let (_a, path_a) = create_test_file(b"same content");
+        let (_b, path_b) = create_test_file(b"other stuff!");
+        let (_c, path_c) = create_test_file(b"same content");
+        let duplicates = find_duplicates(vec![path_a.clone(), path_b, path_c.clone()], None)
+            .await
+            .unwrap();
+
+        assert_eq!(duplicates.groups, vec![vec![path_a, path_c]]);
+        assert_eq!(duplicates.empty_files, None);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_counts_empty_files() {
+        let (_a, path_a) = create_test_file(b"same content");
+        let (_b, path_b) = create_test_file(b"");
+        let (_c, path_c) = create_test_file(b"same content");
+        let (_d, path_d) = create_test_file(b"");
+        let paths = vec![
+            path_a.clone(),
+            path_b.clone(),
+            path_c.clone(),
+            path_d.clone(),
+        ];
+
+        let counted = find_duplicates(paths.clone(), Some(true)).await.unwrap();
+        assert_eq!(
+            counted,
+            Duplicates {
+                groups: vec![vec![path_a.clone(), path_c.clone()]],
+                empty_files: Some(2),
+            }
+        );
+
+        // Without counting, the empty files group together as before
+        let grouped = find_duplicates(paths, None).await.unwrap();
+        assert_eq!(
+            grouped.groups,
+            vec![vec![path_b, path_d], vec![path_a, path_c]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_none() {
+        let (_b, path_b) = create_test_file(b"a bit longer");
+        let (_c, path_c) = create_test_file(b"short");
+        assert!(find_duplicates(vec![path_b, path_c], Some(true))
+            .await
+            .unwrap()
+            .groups
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_errors() {
+        let (_a, path_a) = create_test_file(b"data");
+        assert!(compare_files(path_a, "/nonexistent/file.txt".into())
+            .await
+            .is_err());
+    }
+}
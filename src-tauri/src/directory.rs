@@ -0,0 +1,782 @@
+use crate::algorithms::{self, Algorithm};
+use crate::manifest::portable_relative_name;
+use crate::{calculate_file_hash, HashResult};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::ipc::Channel;
+use walkdir::WalkDir;
+
+/// Number of files hashed at once when the caller doesn't say: one per CPU.
+fn concurrency_limit(max_concurrency: Option<usize>) -> usize {
+    max_concurrency
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .max(1)
+}
+
+/// A file that couldn't be hashed: its full path and the reason.
+type FileError = (String, String);
+
+/// Lists the regular files under `dir` in sorted order. Entries the walk
+/// couldn't read are kept as errors so they can be reported with the rest.
+///
+/// An unreadable subdirectory yields one error and its contents are
+/// skipped; `WalkDir` carries on with the siblings, so a single denied
+/// folder never cuts the listing short.
+fn walk_files(dir: &Path, recursive: bool) -> Vec<Result<PathBuf, FileError>> {
+    let mut walker = WalkDir::new(dir).min_depth(1).sort_by_file_name();
+    if !recursive {
+        walker = walker.max_depth(1);
+    }
+
+    let mut files = Vec::new();
+    for entry in walker {
+        match entry {
+            Ok(entry) if entry.file_type().is_file() => files.push(Ok(entry.into_path())),
+            Ok(_) => {}
+            Err(e) => {
+                let path = e.path().unwrap_or(dir).to_string_lossy().to_string();
+                let reason = e
+                    .io_error()
+                    .map_or_else(|| e.to_string(), |io_error| io_error.to_string());
+                files.push(Err((path, reason)));
+            }
+        }
+    }
+    files
+}
+
+/// Hashes `files`, setting each result's `path` relative to `dir`, and
+/// calls `on_done` with each file's path and size as it finishes.
+///
+/// At most `max_concurrency` files are read at a time; results keep the
+/// order of `files` regardless.
+fn hash_files(
+    dir: &Path,
+    files: &[Result<PathBuf, FileError>],
+    max_concurrency: usize,
+    on_done: impl Fn(&str, u64) + Sync,
+) -> Vec<Result<HashResult, FileError>> {
+    let hash_one = |file: &Result<PathBuf, FileError>| {
+        let path = file.as_ref().map_err(Clone::clone)?;
+        let full_path = path.to_string_lossy().to_string();
+        let mut result = calculate_file_hash(&full_path, &Algorithm::DEFAULT)
+            .map_err(|e| (full_path, e.to_string()))?;
+        result.set_path(&path.strip_prefix(dir).unwrap_or(path).to_string_lossy());
+        Ok(result)
+    };
+    let hash_and_report = |file: &Result<PathBuf, FileError>| {
+        let outcome = hash_one(file);
+        match &outcome {
+            Ok(result) => on_done(&result.path, result.file_size),
+            Err((path, _)) => {
+                let full_path = Path::new(path);
+                on_done(
+                    &full_path
+                        .strip_prefix(dir)
+                        .unwrap_or(full_path)
+                        .to_string_lossy(),
+                    0,
+                )
+            }
+        }
+        outcome
+    };
+
+    // Workers pull the next unclaimed index, so no more than
+    // `max_concurrency` files are open at once
+    let next = AtomicUsize::new(0);
+    let mut indexed: Vec<(usize, Result<HashResult, FileError>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..max_concurrency.min(files.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(file) = files.get(i) else {
+                            return done;
+                        };
+                        done.push((i, hash_and_report(file)));
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("directory hashing thread panicked"))
+            .collect()
+    });
+    indexed.sort_by_key(|(i, _)| *i);
+    indexed.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Minimum time between [`DirProgress`] events, so folders of thousands of
+/// tiny files don't flood the IPC channel.
+const PROGRESS_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Overall progress through a [`hash_directory`] call.
+#[derive(serde::Serialize, Clone, Debug, Default, PartialEq)]
+pub struct DirProgress {
+    pub files_done: u64,
+    pub files_total: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// Relative path of the file that just finished.
+    pub current_path: String,
+}
+
+/// Tallies finished files from the worker threads and passes the running
+/// totals to `emit`, at most once per [`PROGRESS_DEBOUNCE`] apart from the
+/// final event, which is always sent.
+struct ProgressReporter<F> {
+    state: Mutex<(DirProgress, Option<Instant>)>,
+    emit: F,
+}
+
+impl<F: Fn(DirProgress)> ProgressReporter<F> {
+    /// Sizes come from a metadata-only pass over `files`; a file that
+    /// can't be stat'ed counts as empty.
+    fn new(files: &[Result<PathBuf, FileError>], emit: F) -> ProgressReporter<F> {
+        let bytes_total = files
+            .iter()
+            .filter_map(|file| file.as_ref().ok())
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        let progress = DirProgress {
+            files_total: files.len() as u64,
+            bytes_total,
+            ..Default::default()
+        };
+        ProgressReporter {
+            state: Mutex::new((progress, None)),
+            emit,
+        }
+    }
+
+    fn file_done(&self, path: &str, bytes: u64) {
+        let mut state = self.state.lock().unwrap();
+        let (progress, last_emitted) = &mut *state;
+        progress.files_done += 1;
+        progress.bytes_done += bytes;
+        progress.current_path = path.to_string();
+        let finished = progress.files_done == progress.files_total;
+        if finished || last_emitted.is_none_or(|at| at.elapsed() >= PROGRESS_DEBOUNCE) {
+            *last_emitted = Some(Instant::now());
+            (self.emit)(progress.clone());
+        }
+    }
+}
+
+/// Results of [`hash_directory`].
+#[derive(serde::Serialize, Debug, Default)]
+pub struct DirectoryHashes {
+    pub results: Vec<HashResult>,
+    /// Number of zero-byte files, only counted when asked for.
+    pub empty_files: Option<usize>,
+}
+
+/// Appends a `<path>\t<error>` line per failure to the log at `log_path`,
+/// creating it if needed.
+fn append_error_log(log_path: &Path, failures: &[FileError]) -> Result<(), String> {
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+        .map_err(|e| format!("{}: {}", log_path.display(), e))?;
+    for (path, error) in failures {
+        writeln!(log, "{}\t{}", path, error)
+            .map_err(|e| format!("{}: {}", log_path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Hashes the files under `dir`, or only its direct children when
+/// `recursive` is false. Each result's `path` is relative to `dir`.
+///
+/// `max_concurrency` caps how many files are read in parallel (default one
+/// per CPU); set it to 1 on spinning disks to avoid seek thrashing.
+///
+/// A file or subdirectory that can't be read never stops the walk. Without
+/// `error_log_path` it comes back as a result with no digests and the
+/// reason under `errors["file"]`; with it, failures are appended to that
+/// log and only the files that hashed are returned.
+///
+/// `count_empty` also reports how many of the files were zero bytes, which
+/// in a downloaded folder usually means truncated transfers.
+///
+/// `on_progress` receives [`DirProgress`] as files finish, for an overall
+/// progress bar across the folder.
+#[tauri::command]
+pub async fn hash_directory(
+    dir: String,
+    recursive: bool,
+    max_concurrency: Option<usize>,
+    error_log_path: Option<String>,
+    count_empty: Option<bool>,
+    on_progress: Channel<DirProgress>,
+) -> Result<DirectoryHashes, String> {
+    let results = hash_tree(
+        Path::new(&dir),
+        recursive,
+        concurrency_limit(max_concurrency),
+        error_log_path.as_deref().map(Path::new),
+        |progress| {
+            // A closed channel just means nobody is watching any more
+            let _ = on_progress.send(progress);
+        },
+    )?;
+    let empty_files = count_empty
+        .unwrap_or(false)
+        .then(|| results.iter().filter(|result| result.is_empty).count());
+    Ok(DirectoryHashes {
+        results,
+        empty_files,
+    })
+}
+
+/// Stands in for a file that couldn't be hashed: its path relative to
+/// `root` and the reason under `errors["file"]`, with every digest empty.
+fn failed_result(root: &Path, (path, error): FileError) -> HashResult {
+    let full_path = Path::new(&path);
+    let mut result = HashResult::default();
+    result.set_path(
+        &full_path
+            .strip_prefix(root)
+            .unwrap_or(full_path)
+            .to_string_lossy(),
+    );
+    result.errors.insert("file".to_string(), error);
+    result
+}
+
+fn hash_tree(
+    root: &Path,
+    recursive: bool,
+    max_concurrency: usize,
+    error_log_path: Option<&Path>,
+    on_progress: impl Fn(DirProgress) + Sync,
+) -> Result<Vec<HashResult>, String> {
+    if !root.is_dir() {
+        return Err(format!("{}: not a directory", root.display()));
+    }
+    let files = walk_files(root, recursive);
+    let reporter = ProgressReporter::new(&files, on_progress);
+    let outcomes = hash_files(root, &files, max_concurrency, |path, bytes| {
+        reporter.file_done(path, bytes)
+    });
+
+    let Some(log_path) = error_log_path else {
+        return Ok(outcomes
+            .into_iter()
+            .map(|outcome| outcome.unwrap_or_else(|failure| failed_result(root, failure)))
+            .collect());
+    };
+    let mut results = Vec::new();
+    let mut failures = Vec::new();
+    for outcome in outcomes {
+        match outcome {
+            Ok(result) => results.push(result),
+            Err(failure) => failures.push(failure),
+        }
+    }
+    append_error_log(log_path, &failures)?;
+    Ok(results)
+}
+
+/// SHA-256 of every file under `dir`, keyed by forward-slash relative
+/// path.
+fn tree_hashes(dir: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut hashes = BTreeMap::new();
+    for entry in WalkDir::new(dir).min_depth(1) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let hash = File::open(entry.path())
+            .and_then(|file| algorithms::hash_reader(file, Algorithm::Sha256))
+            .map_err(|e| format!("{}: {}", entry.path().display(), e))?;
+        hashes.insert(portable_relative_name(entry.path(), dir), hash);
+    }
+    Ok(hashes)
+}
+
+/// Builds the `path\0sha256\n` listing for every file under `dir`, sorted
+/// by forward-slash relative path so enumeration order doesn't matter.
+fn root_listing(dir: &Path) -> Result<String, String> {
+    let mut listing = String::new();
+    for (path, hash) in tree_hashes(dir)? {
+        listing.push_str(&path);
+        listing.push('\0');
+        listing.push_str(&hash);
+        listing.push('\n');
+    }
+    Ok(listing)
+}
+
+/// Returns a single SHA-256 standing for the whole tree: the digest of each
+/// file's relative path and SHA-256, in sorted order. Renaming, adding or
+/// changing any file changes the root.
+#[tauri::command]
+pub async fn directory_root_hash(dir: String) -> Result<String, String> {
+    let root = Path::new(&dir);
+    if !root.is_dir() {
+        return Err(format!("{}: not a directory", dir));
+    }
+    let listing = root_listing(root)?;
+    algorithms::hash_reader(listing.as_bytes(), Algorithm::Sha256).map_err(|e| e.to_string())
+}
+
+/// How two directory trees differ. Each list holds forward-slash paths
+/// relative to the tree roots, sorted.
+#[derive(serde::Serialize, Debug, Default, PartialEq)]
+pub struct DirDiff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    /// Present in both with different contents.
+    pub differing: Vec<String>,
+    pub identical: Vec<String>,
+}
+
+fn diff_trees(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> DirDiff {
+    let mut diff = DirDiff::default();
+    for (path, hash_a) in a {
+        match b.get(path) {
+            None => diff.only_in_a.push(path.clone()),
+            Some(hash_b) if hash_b == hash_a => diff.identical.push(path.clone()),
+            Some(_) => diff.differing.push(path.clone()),
+        }
+    }
+    diff.only_in_b = b
+        .keys()
+        .filter(|path| !a.contains_key(*path))
+        .cloned()
+        .collect();
+    diff
+}
+
+/// Hashes every file in both trees and reports which relative paths exist
+/// on only one side, which differ, and which match.
+#[tauri::command]
+pub async fn diff_directories(dir_a: String, dir_b: String) -> Result<DirDiff, String> {
+    for dir in [&dir_a, &dir_b] {
+        if !Path::new(dir).is_dir() {
+            return Err(format!("{}: not a directory", dir));
+        }
+    }
+    let hashes_a = tree_hashes(Path::new(&dir_a))?;
+    let hashes_b = tree_hashes(Path::new(&dir_b))?;
+    Ok(diff_trees(&hashes_a, &hashes_b))
+}
+
+/// Assumed hashing speed for [`estimate_hash_job`] when the caller has no
+/// measurement of their own, in MB/s (10^6 bytes).
+const DEFAULT_ESTIMATE_MBPS: f64 = 500.0;
+
+/// Rough size of a hashing job, computed from metadata without reading.
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct JobEstimate {
+    pub total_bytes: u64,
+    pub file_count: u64,
+    pub est_seconds: f64,
+}
+
+fn job_size(paths: &[String]) -> Result<(u64, u64), String> {
+    let mut total_bytes = 0;
+    let mut file_count = 0;
+    for path in paths {
+        for entry in WalkDir::new(path) {
+            let entry = entry.map_err(|e| e.to_string())?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let metadata = entry
+                .metadata()
+                .map_err(|e| format!("{}: {}", entry.path().display(), e))?;
+            total_bytes += metadata.len();
+            file_count += 1;
+        }
+    }
+    Ok((total_bytes, file_count))
+}
+
+/// Sums the sizes of `paths`, walking into directories, and estimates how
+/// long hashing them would take at `throughput_mbps` (default 500 MB/s).
+#[tauri::command]
+pub async fn estimate_hash_job(
+    paths: Vec<String>,
+    throughput_mbps: Option<f64>,
+) -> Result<JobEstimate, String> {
+    let throughput = throughput_mbps.unwrap_or(DEFAULT_ESTIMATE_MBPS);
+    if !(throughput > 0.0 && throughput.is_finite()) {
+        return Err("throughput must be a positive number".to_string());
+    }
+    let (total_bytes, file_count) = job_size(&paths)?;
+    Ok(JobEstimate {
+        total_bytes,
+        file_count,
+        est_seconds: total_bytes as f64 / 1_000_000.0 / throughput,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn no_progress() -> Channel<DirProgress> {
+        Channel::new(|_| Ok(()))
+    }
+
+    fn create_test_tree() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("a.txt"), b"Content A").unwrap();
+        fs::write(root.join("b.txt"), b"Content B").unwrap();
+        fs::create_dir_all(root.join("sub").join("deeper")).unwrap();
+        fs::write(root.join("sub").join("c.txt"), b"Content C").unwrap();
+        fs::write(root.join("sub").join("deeper").join("d.txt"), b"Content D").unwrap();
+        temp_dir
+    }
+
+    #[tokio::test]
+    async fn test_hash_directory_recursive() {
+        let temp_dir = create_test_tree();
+        let dir = temp_dir.path().to_string_lossy().to_string();
+        let results = hash_directory(dir, true, None, None, None, no_progress())
+            .await
+            .unwrap()
+            .results;
+
+        assert_eq!(results.len(), 4);
+        let nested = Path::new("sub").join("deeper").join("d.txt");
+        assert!(results
+            .iter()
+            .any(|r| Path::new(&r.path) == nested.as_path() && r.file_size == 9));
+    }
+
+    #[tokio::test]
+    async fn test_hash_directory_top_level_only() {
+        let temp_dir = create_test_tree();
+        let dir = temp_dir.path().to_string_lossy().to_string();
+        let results = hash_directory(dir, false, None, None, None, no_progress())
+            .await
+            .unwrap()
+            .results;
+
+        let paths: Vec<&str> = results.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_hash_directory_concurrency_limits() {
+        let temp_dir = create_test_tree();
+        let dir = temp_dir.path().to_string_lossy().to_string();
+        let serial = hash_directory(dir.clone(), true, Some(1), None, None, no_progress())
+            .await
+            .unwrap()
+            .results;
+        let wide = hash_directory(dir.clone(), true, Some(64), None, None, no_progress())
+            .await
+            .unwrap()
+            .results;
+        // Zero is treated as one rather than hashing nothing
+        let zero = hash_directory(dir, true, Some(0), None, None, no_progress())
+            .await
+            .unwrap()
+            .results;
+
+        assert_eq!(serial.len(), 4);
+        let paths = |results: &[HashResult]| {
+            results
+                .iter()
+                .map(|r| (r.path.clone(), r.sha256.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(&serial), paths(&wide));
+        assert_eq!(paths(&serial), paths(&zero));
+    }
+
+    #[test]
+    fn test_failures_are_logged_and_skipped() {
+        let temp_dir = create_test_tree();
+        let root = temp_dir.path();
+        let missing = root.join("vanished.txt");
+        let files = vec![Ok(root.join("a.txt")), Ok(missing.clone())];
+
+        let outcomes = hash_files(root, &files, 2, |_, _| {});
+        assert_eq!(outcomes[0].as_ref().unwrap().path, "a.txt");
+        let failure = outcomes[1].clone().unwrap_err();
+        assert_eq!(failure.0, missing.to_string_lossy());
+
+        let log_path = root.join("errors.log");
+        append_error_log(&log_path, std::slice::from_ref(&failure)).unwrap();
+        append_error_log(&log_path, std::slice::from_ref(&failure)).unwrap();
+        let log = fs::read_to_string(&log_path).unwrap();
+        let line = format!("{}\t{}\n", failure.0, failure.1);
+        assert_eq!(log, line.repeat(2));
+    }
+
+    #[tokio::test]
+    async fn test_hash_directory_with_error_log() {
+        let temp_dir = create_test_tree();
+        let log_dir = TempDir::new().unwrap();
+        let log_path = log_dir.path().join("errors.log");
+        let dir = temp_dir.path().to_string_lossy().to_string();
+
+        let results = hash_directory(
+            dir,
+            true,
+            None,
+            Some(log_path.to_string_lossy().to_string()),
+            None,
+            no_progress(),
+        )
+        .await
+        .unwrap()
+        .results;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_denied_subdir_does_not_stop_walk() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = create_test_tree();
+        let locked = temp_dir.path().join("sub");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Root ignores directory permissions, so there is nothing to test
+        if fs::read_dir(&locked).is_ok() {
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let log_dir = TempDir::new().unwrap();
+        let log_path = log_dir.path().join("errors.log");
+        let outcome = hash_tree(temp_dir.path(), true, 2, Some(&log_path), |_| {});
+        let files = walk_files(temp_dir.path(), true);
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let failed: Vec<_> = files.iter().filter_map(|f| f.as_ref().err()).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, locked.to_string_lossy());
+
+        let mut paths: Vec<_> = outcome.unwrap().into_iter().map(|r| r.path).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+        let log = fs::read_to_string(&log_path).unwrap();
+        assert!(
+            log.starts_with(&format!("{}\t", locked.display())),
+            "{}",
+            log
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_denied_subdir_without_error_log() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = create_test_tree();
+        let locked = temp_dir.path().join("sub");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Root ignores directory permissions, so there is nothing to test
+        if fs::read_dir(&locked).is_ok() {
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let outcome = hash_tree(temp_dir.path(), true, 2, None, |_| {});
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let results = outcome.unwrap();
+        let (failed, hashed): (Vec<_>, Vec<_>) = results.iter().partition(|r| !r.errors.is_empty());
+        let mut paths: Vec<_> = hashed.iter().map(|r| r.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].path, "sub");
+        assert!(failed[0].errors.contains_key("file"));
+        assert!(failed[0].sha256.is_empty());
+    }
+
+    #[test]
+    fn test_directory_progress_ends_complete() {
+        let temp_dir = create_test_tree();
+        let events = Mutex::new(Vec::new());
+        let results = hash_tree(temp_dir.path(), true, 2, None, |progress| {
+            events.lock().unwrap().push(progress)
+        })
+        .unwrap();
+        assert_eq!(results.len(), 4);
+
+        let events = events.into_inner().unwrap();
+        // The first file always reports; the rest may be debounced away
+        assert!(!events.is_empty());
+        let last = events.last().unwrap();
+        assert_eq!(last.files_done, last.files_total);
+        assert_eq!(last.files_total, 4);
+        assert_eq!(last.bytes_done, 36);
+        assert_eq!(last.bytes_total, 36);
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].files_done < pair[1].files_done));
+    }
+
+    #[test]
+    fn test_progress_reporter_debounces() {
+        let files: Vec<Result<PathBuf, FileError>> = (0..1000)
+            .map(|i| Ok(PathBuf::from(format!("/nonexistent/{}", i))))
+            .collect();
+        let events = Mutex::new(Vec::new());
+        let reporter =
+            ProgressReporter::new(&files, |progress| events.lock().unwrap().push(progress));
+        for i in 0..1000 {
+            reporter.file_done(&i.to_string(), 1);
+        }
+
+        let events = events.into_inner().unwrap();
+        assert!(events.len() < 1000);
+        assert_eq!(events[0].current_path, "0");
+        assert_eq!(events.last().unwrap().files_done, 1000);
+        assert_eq!(events.last().unwrap().bytes_done, 1000);
+    }
+
+    #[test]
+    fn test_concurrency_limit_defaults() {
+        assert!(concurrency_limit(None) >= 1);
+        assert_eq!(concurrency_limit(Some(0)), 1);
+        assert_eq!(concurrency_limit(Some(3)), 3);
+    }
+
+    #[tokio::test]
+    async fn test_hash_directory_counts_empty_files() {
+        let temp_dir = create_test_tree();
+        fs::write(temp_dir.path().join("truncated.iso"), b"").unwrap();
+        fs::write(temp_dir.path().join("sub").join("stub.bin"), b"").unwrap();
+        let dir = temp_dir.path().to_string_lossy().to_string();
+
+        let counted = hash_directory(dir.clone(), true, None, None, Some(true), no_progress())
+            .await
+            .unwrap();
+        assert_eq!(counted.results.len(), 6);
+        assert_eq!(counted.empty_files, Some(2));
+
+        let uncounted = hash_directory(dir, true, None, None, None, no_progress())
+            .await
+            .unwrap();
+        assert_eq!(uncounted.empty_files, None);
+    }
+
+    #[tokio::test]
+    async fn test_hash_directory_rejects_file() {
+        let temp_dir = create_test_tree();
+        let file = temp_dir.path().join("a.txt").to_string_lossy().to_string();
+
+        assert!(hash_directory(file, true, None, None, None, no_progress())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_directory_root_hash_stable_and_sensitive() {
+        let first = create_test_tree();
+        let second = create_test_tree();
+        let root_of = |dir: &TempDir| dir.path().to_string_lossy().to_string();
+
+        let root_a = directory_root_hash(root_of(&first)).await.unwrap();
+        let root_b = directory_root_hash(root_of(&second)).await.unwrap();
+        assert_eq!(root_a, root_b);
+        assert_eq!(root_a.len(), 64);
+
+        fs::write(
+            second.path().join("sub").join("deeper").join("d.txt"),
+            b"Content E",
+        )
+        .unwrap();
+        let changed = directory_root_hash(root_of(&second)).await.unwrap();
+        assert_ne!(root_a, changed);
+    }
+
+    #[test]
+    fn test_root_listing_format() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("z")).unwrap();
+        fs::write(temp_dir.path().join("z").join("inner.txt"), b"").unwrap();
+        fs::write(temp_dir.path().join("b.txt"), b"").unwrap();
+
+        let empty = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(
+            root_listing(temp_dir.path()).unwrap(),
+            format!("b.txt\0{}\nz/inner.txt\0{}\n", empty, empty)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diff_directories() {
+        let a = create_test_tree();
+        let b = create_test_tree();
+        fs::write(a.path().join("sub").join("c.txt"), b"Changed C").unwrap();
+        fs::write(a.path().join("only_a.txt"), b"A only").unwrap();
+        fs::write(b.path().join("sub").join("only_b.txt"), b"B only").unwrap();
+        let dir_of = |dir: &TempDir| dir.path().to_string_lossy().to_string();
+
+        let diff = diff_directories(dir_of(&a), dir_of(&b)).await.unwrap();
+        assert_eq!(
+            diff,
+            DirDiff {
+                only_in_a: vec!["only_a.txt".to_string()],
+                only_in_b: vec!["sub/only_b.txt".to_string()],
+                differing: vec!["sub/c.txt".to_string()],
+                identical: vec![
+                    "a.txt".to_string(),
+                    "b.txt".to_string(),
+                    "sub/deeper/d.txt".to_string(),
+                ],
+            }
+        );
+
+        let file = a.path().join("a.txt").to_string_lossy().to_string();
+        assert!(diff_directories(dir_of(&a), file).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_estimate_hash_job() {
+        let temp_dir = create_test_tree();
+        let extra = TempDir::new().unwrap();
+        let single = extra.path().join("single.bin");
+        fs::write(&single, vec![0u8; 2_000_000]).unwrap();
+
+        let estimate = estimate_hash_job(
+            vec![
+                temp_dir.path().to_string_lossy().to_string(),
+                single.to_string_lossy().to_string(),
+            ],
+            Some(1.0),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(estimate.file_count, 5);
+        assert_eq!(estimate.total_bytes, 4 * 9 + 2_000_000);
+        assert!((estimate.est_seconds - 2.000036).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_hash_job_errors() {
+        assert!(estimate_hash_job(vec!["/nonexistent/dir".into()], None)
+            .await
+            .is_err());
+        assert!(estimate_hash_job(vec![], Some(0.0)).await.is_err());
+        assert_eq!(estimate_hash_job(vec![], None).await.unwrap().file_count, 0);
+    }
+}
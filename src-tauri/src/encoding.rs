@@ -0,0 +1,251 @@
+use crate::algorithms::Algorithm;
+use crate::{calculate_file_hash, HashResult};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use data_encoding::{BASE32, BASE32_NOPAD, HEXLOWER};
+
+/// Digests encoded as text other than hex, such as base64 or base32.
+#[derive(serde::Serialize, Debug, Default)]
+pub struct EncodedHashResult {
+    pub path: String,
+    pub file_size: u64,
+    pub md5: String,
+    pub sha1: String,
+    pub sha224: String,
+    pub sha256: String,
+    pub sha384: String,
+    pub sha512: String,
+    pub blake3: String,
+    pub sha3_256: String,
+    pub sha3_512: String,
+    pub xxh3: String,
+    pub adler32: String,
+    /// Opt-in algorithms, left empty since this always uses the defaults.
+    pub ripemd160: String,
+    pub whirlpool: String,
+    pub tiger: String,
+    pub gost94: String,
+}
+
+/// Digests encoded as standard padded base64, matching the form used by
+/// Subresource Integrity and npm `integrity` fields.
+pub type HashResultB64 = EncodedHashResult;
+
+/// Digests encoded as RFC 4648 base32, which survives case-insensitive
+/// environments such as DNS labels and TOTP secrets.
+pub type HashResultB32 = EncodedHashResult;
+
+impl From<HashResult> for EncodedHashResult {
+    fn from(result: HashResult) -> EncodedHashResult {
+        EncodedHashResult {
+            path: result.path,
+            file_size: result.file_size,
+            md5: result.md5,
+            sha1: result.sha1,
+            sha224: result.sha224,
+            sha256: result.sha256,
+            sha384: result.sha384,
+            sha512: result.sha512,
+            blake3: result.blake3,
+            sha3_256: result.sha3_256,
+            sha3_512: result.sha3_512,
+            xxh3: result.xxh3,
+            adler32: result.adler32,
+            ripemd160: result.ripemd160,
+            whirlpool: result.whirlpool,
+            tiger: result.tiger,
+            gost94: result.gost94,
+        }
+    }
+}
+
+/// Hashes the file with the default algorithms like `calculate_checksum`,
+/// then re-encodes each hex digest with `encode`.
+fn to_encoded(path: &str, encode: impl Fn(&[u8]) -> String) -> Result<EncodedHashResult, String> {
+    let mut result = calculate_file_hash(path, &Algorithm::DEFAULT).map_err(|e| e.to_string())?;
+    if let Some((id, e)) = result.errors.iter().next() {
+        return Err(format!("{}: {}", id, e));
+    }
+    for algorithm in Algorithm::DEFAULT {
+        let digest = result.digest_mut(algorithm);
+        let raw = HEXLOWER
+            .decode(digest.as_bytes())
+            .map_err(|e| e.to_string())?;
+        *digest = encode(&raw);
+    }
+    Ok(result.into())
+}
+
+#[tauri::command]
+pub async fn calculate_checksum_base64(path: String) -> Result<HashResultB64, String> {
+    to_encoded(&path, |digest| STANDARD.encode(digest))
+}
+
+/// Computes the default algorithms with each digest in uppercase base32.
+/// Padded with `=` to a multiple of 8 characters unless `padding` is
+/// `Some(false)`.
+#[tauri::command]
+pub async fn calculate_checksum_base32(
+    path: String,
+    padding: Option<bool>,
+) -> Result<HashResultB32, String> {
+    let encoding = if padding.unwrap_or(true) {
+        &BASE32
+    } else {
+        &BASE32_NOPAD
+    };
+    to_encoded(&path, |digest| encoding.encode(digest))
+}
+
+/// Inserts `separator` every `group_size` characters of `hash` for
+/// display, e.g. `d7a8 fbb3 07d7`. The last group may be shorter. A
+/// `group_size` of 0 returns `hash` unchanged.
+#[tauri::command]
+pub fn format_hash(hash: String, group_size: usize, separator: String) -> String {
+    if group_size == 0 {
+        return hash;
+    }
+    let chars: Vec<char> = hash.chars().collect();
+    chars
+        .chunks(group_size)
+        .map(|group| group.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(&separator)
+}
+
+/// Shortens `s` to at most `max` characters by replacing its middle with
+/// `…`, e.g. `d7a8fbb3…37c9e592`, so a long digest or file name can't
+/// overflow a tooltip. Strings that already fit are returned unchanged.
+pub fn truncate_middle(s: &str, max: usize) -> String {
+    let len = s.chars().count();
+    if len <= max {
+        return s.to_string();
+    }
+    let Some(keep) = max.checked_sub(1) else {
+        return String::new();
+    };
+    // Odd lengths give the extra character to the start, which is what
+    // people compare first
+    let tail = keep / 2;
+    let head = keep - tail;
+    let mut truncated: String = s.chars().take(head).collect();
+    truncated.push('…');
+    truncated.extend(s.chars().skip(len - tail));
+    debug_assert!(truncated.chars().count() <= max);
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_file(content: &[u8]) -> (NamedTempFile, String) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        (file, path)
+    }
+
+    #[tokio::test]
+    async fn test_base64_empty_file() {
+        let (_file, path) = create_test_file(b"");
+        let result = calculate_checksum_base64(path).await.unwrap();
+
+        assert_eq!(
+            result.sha256,
+            "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+        );
+        assert_eq!(result.md5, "1B2M2Y8AsgTpgAmY7PhCfg==");
+        assert_eq!(result.file_size, 0);
+    }
+
+    #[tokio::test]
+    async fn test_base64_field_order_matches_algorithms() {
+        let (_file, path) = create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let result = calculate_checksum_base64(path).await.unwrap();
+
+        // 64-byte digests encode to 88 characters, 32-byte ones to 44
+        assert_eq!(result.sha512.len(), 88);
+        assert_eq!(result.sha3_512.len(), 88);
+        assert_eq!(result.blake3.len(), 44);
+        assert_eq!(result.sha1, "L9ThxnotKPzthJ7hu3bnORuT6xI=");
+    }
+
+    #[tokio::test]
+    async fn test_base64_missing_file() {
+        assert!(calculate_checksum_base64("/nonexistent/file".into())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_base32_sha1_known_vector() {
+        let (_file, path) = create_test_file(b"abc");
+        let result = calculate_checksum_base32(path, None).await.unwrap();
+
+        // SHA-1 is 20 bytes, a multiple of 5, so it never needs padding
+        assert_eq!(result.sha1, "VGMT4NSHA2AWVOR6EVYXQUGCNSONBWE5");
+        assert_eq!(result.file_size, 3);
+    }
+
+    #[tokio::test]
+    async fn test_base32_padding_option() {
+        let (_file, path) = create_test_file(b"");
+        let padded = calculate_checksum_base32(path.clone(), Some(true))
+            .await
+            .unwrap();
+        let unpadded = calculate_checksum_base32(path, Some(false)).await.unwrap();
+
+        assert_eq!(padded.md5, "2QOYZWMPACZAJ2MABGMOZ6CCPY======");
+        assert_eq!(unpadded.md5, "2QOYZWMPACZAJ2MABGMOZ6CCPY");
+        assert_eq!(padded.sha1, unpadded.sha1);
+    }
+
+    #[test]
+    fn test_format_hash_groups_of_four() {
+        assert_eq!(
+            format_hash("d7a8fbb307d780".into(), 4, " ".into()),
+            "d7a8 fbb3 07d7 80"
+        );
+        assert_eq!(format_hash("d7a8fbb3".into(), 4, " ".into()), "d7a8 fbb3");
+    }
+
+    #[test]
+    fn test_format_hash_groups_of_eight() {
+        assert_eq!(
+            format_hash("9e107d9d372bb6826bd8".into(), 8, "-".into()),
+            "9e107d9d-372bb682-6bd8"
+        );
+    }
+
+    #[test]
+    fn test_format_hash_zero_group_unchanged() {
+        assert_eq!(format_hash("d7a8fbb3".into(), 0, " ".into()), "d7a8fbb3");
+        assert_eq!(format_hash(String::new(), 4, " ".into()), "");
+    }
+
+    #[test]
+    fn test_truncate_middle_shorter_than_max() {
+        assert_eq!(truncate_middle("d7a8fbb3", 17), "d7a8fbb3");
+        assert_eq!(truncate_middle("", 4), "");
+    }
+
+    #[test]
+    fn test_truncate_middle_equal_to_max() {
+        assert_eq!(truncate_middle("d7a8fbb3", 8), "d7a8fbb3");
+    }
+
+    #[test]
+    fn test_truncate_middle_longer_than_max() {
+        let sha256 = "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592";
+        assert_eq!(truncate_middle(sha256, 17), "d7a8fbb3…37c9e592");
+        assert_eq!(truncate_middle(sha256, 16), "d7a8fbb3…7c9e592");
+        assert_eq!(truncate_middle("fox.txt", 1), "…");
+        assert_eq!(truncate_middle("fox.txt", 0), "");
+        // Counts characters, not bytes
+        assert_eq!(truncate_middle("ééééé", 3), "é…é");
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+use std::io;
+
+/// Failure returned to the frontend by `calculate_checksum`, serialized as
+/// `{ "kind": "NotFound" }` or `{ "kind": "Io", "message": "..." }` so the UI
+/// can branch on `kind` instead of parsing OS messages.
+#[derive(serde::Serialize, Debug, PartialEq)]
+#[serde(tag = "kind", content = "message")]
+pub enum ChecksumError {
+    NotFound,
+    PermissionDenied,
+    IsDirectory,
+    /// The path is a symlink and following symlinks was turned off.
+    IsSymlink,
+    /// A bad argument, such as an unknown algorithm id.
+    InvalidArgument(String),
+    Io(String),
+}
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChecksumError::NotFound => f.write_str("file not found"),
+            ChecksumError::PermissionDenied => f.write_str("permission denied"),
+            ChecksumError::IsDirectory => f.write_str("path is a directory"),
+            ChecksumError::IsSymlink => f.write_str("path is a symlink"),
+            ChecksumError::InvalidArgument(message) | ChecksumError::Io(message) => {
+                f.write_str(message)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for ChecksumError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => ChecksumError::NotFound,
+            io::ErrorKind::PermissionDenied => ChecksumError::PermissionDenied,
+            io::ErrorKind::IsADirectory => ChecksumError::IsDirectory,
+            _ => ChecksumError::Io(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_error_kind() {
+        let not_found = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(ChecksumError::from(not_found), ChecksumError::NotFound);
+
+        let other = io::Error::other("disk on fire");
+        assert_eq!(
+            ChecksumError::from(other),
+            ChecksumError::Io("disk on fire".to_string())
+        );
+    }
+
+    #[test]
+    fn test_display_is_readable() {
+        assert_eq!(ChecksumError::NotFound.to_string(), "file not found");
+        assert_eq!(ChecksumError::Io("boom".to_string()).to_string(), "boom");
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        assert_eq!(
+            serde_json::to_string(&ChecksumError::PermissionDenied).unwrap(),
+            r#"{"kind":"PermissionDenied"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&ChecksumError::Io("boom".to_string())).unwrap(),
+            r#"{"kind":"Io","message":"boom"}"#
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::HashResult;
+use std::fs;
+use std::path::Path;
+
+/// Creates the parent directories of `out_path` so exports can target a
+/// folder that doesn't exist yet.
+fn ensure_parent_dir(out_path: &Path) -> Result<(), String> {
+    match out_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e)),
+        _ => Ok(()),
+    }
+}
+
+fn write_export(out_path: &str, contents: &[u8]) -> Result<(), String> {
+    let out_path = Path::new(out_path);
+    ensure_parent_dir(out_path)?;
+    fs::write(out_path, contents).map_err(|e| format!("cannot write {}: {}", out_path.display(), e))
+}
+
+/// Saves the results as pretty-printed JSON.
+#[tauri::command]
+pub async fn export_results_json(results: Vec<HashResult>, out_path: String) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&results).map_err(|e| e.to_string())?;
+    write_export(&out_path, json.as_bytes())
+}
+
+const CSV_HEADER: [&str; 8] = [
+    "path",
+    "file_size",
+    "md5",
+    "sha1",
+    "sha256",
+    "sha512",
+    "modified",
+    "created",
+];
+
+fn results_to_csv(results: &[HashResult]) -> Result<Vec<u8>, String> {
+    // The csv writer quotes fields containing commas or quotes per RFC 4180
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_HEADER).map_err(|e| e.to_string())?;
+    for result in results {
+        let time = |seconds: Option<u64>| seconds.map(|s| s.to_string()).unwrap_or_default();
+        writer
+            .write_record([
+                result.path.as_str(),
+                &result.file_size.to_string(),
+                &result.md5,
+                &result.sha1,
+                &result.sha256,
+                &result.sha512,
+                &time(result.modified),
+                &time(result.created),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+/// Saves the results as CSV with one row per file, for spreadsheets.
+#[tauri::command]
+pub async fn export_results_csv(results: Vec<HashResult>, out_path: String) -> Result<(), String> {
+    let csv = results_to_csv(&results)?;
+    write_export(&out_path, &csv)
+}
+
+/// Saves the results as CBOR, a binary encoding that is noticeably smaller
+/// than the pretty-printed JSON for large batches. Read it back with
+/// [`import_results`].
+#[tauri::command]
+pub async fn export_results_cbor(results: Vec<HashResult>, out_path: String) -> Result<(), String> {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&results, &mut cbor).map_err(|e| e.to_string())?;
+    write_export(&out_path, &cbor)
+}
+
+/// Loads results saved by [`export_results_cbor`] or
+/// [`export_results_json`], telling them apart by the first byte.
+#[tauri::command]
+pub async fn import_results(in_path: String) -> Result<Vec<HashResult>, String> {
+    let contents = fs::read(&in_path).map_err(|e| format!("cannot read {}: {}", in_path, e))?;
+    let parsed = if contents.trim_ascii_start().starts_with(b"[") {
+        serde_json::from_slice(&contents).map_err(|e| e.to_string())
+    } else {
+        ciborium::from_reader(contents.as_slice()).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| format!("{}: {}", in_path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::Algorithm;
+    use crate::calculate_file_hash;
+    use tempfile::TempDir;
+
+    fn sample_results(dir: &TempDir) -> Vec<HashResult> {
+        let file_path = dir.path().join("sample.txt");
+        fs::write(&file_path, b"export me").unwrap();
+        vec![
+            calculate_file_hash(file_path.to_str().unwrap(), &Algorithm::ALL).unwrap(),
+            calculate_file_hash(file_path.to_str().unwrap(), &[Algorithm::Sha256]).unwrap(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_export_json_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let results = sample_results(&temp_dir);
+        let out_path = temp_dir.path().join("nested").join("report.json");
+
+        export_results_json(results.clone(), out_path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+
+        let read_back: Vec<HashResult> =
+            serde_json::from_str(&fs::read_to_string(&out_path).unwrap()).unwrap();
+        assert_eq!(read_back, results);
+    }
+
+    #[tokio::test]
+    async fn test_export_cbor_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let results = sample_results(&temp_dir);
+        let cbor_path = temp_dir.path().join("report.cbor");
+        let json_path = temp_dir.path().join("report.json");
+
+        export_results_cbor(results.clone(), cbor_path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        export_results_json(results.clone(), json_path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+
+        let from_cbor = import_results(cbor_path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        let from_json = import_results(json_path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+        assert_eq!(from_cbor, results);
+        assert_eq!(from_json, results);
+        assert!(fs::metadata(&cbor_path).unwrap().len() < fs::metadata(&json_path).unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_garbage() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("junk.bin");
+        fs::write(&path, [0xffu8, 0x00, 0x13]).unwrap();
+
+        assert!(import_results(path.to_string_lossy().to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_quotes_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = HashResult {
+            path: "dir/a,\"b\".txt".to_string(),
+            md5: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
+            file_size: 0,
+            modified: Some(1700000000),
+            created: None,
+            ..Default::default()
+        };
+        let out_path = temp_dir.path().join("report.csv");
+
+        export_results_csv(vec![result], out_path.to_string_lossy().to_string())
+            .await
+            .unwrap();
+
+        let csv = fs::read_to_string(&out_path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "path,file_size,md5,sha1,sha256,sha512,modified,created"
+        );
+        assert_eq!(
+            lines[1],
+            "\"dir/a,\"\"b\"\".txt\",0,d41d8cd98f00b204e9800998ecf8427e,,,,1700000000,"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_export_json_unwritable_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let results = sample_results(&temp_dir);
+
+        // The target is an existing directory, so it can't be written as a file
+        let result =
+            export_results_json(results, temp_dir.path().to_string_lossy().to_string()).await;
+        assert!(result.unwrap_err().starts_with("cannot write"));
+    }
+}
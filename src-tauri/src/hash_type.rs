@@ -0,0 +1,69 @@
+use crate::algorithms::Algorithm;
+
+/// Guesses which algorithm produced a hex digest from its length. 64-char
+/// digests are reported as sha256, the most commonly published of the
+/// algorithms that share that length.
+pub fn guess_algorithm(hash: &str) -> Option<&'static str> {
+    let hash = hash.trim();
+    if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let algorithm = match hash.len() {
+        32 => Algorithm::Md5,
+        40 => Algorithm::Sha1,
+        64 => Algorithm::Sha256,
+        128 => Algorithm::Sha512,
+        _ => return None,
+    };
+    Some(algorithm.id())
+}
+
+/// Returns the likely algorithm id for a pasted hash, or `None` when it
+/// isn't hex or has an unrecognised length.
+#[tauri::command]
+pub async fn detect_hash_type(hash: String) -> Option<String> {
+    guess_algorithm(&hash).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_each_length() {
+        assert_eq!(
+            guess_algorithm("d41d8cd98f00b204e9800998ecf8427e"),
+            Some("md5")
+        );
+        assert_eq!(
+            guess_algorithm("da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            Some("sha1")
+        );
+        assert_eq!(
+            guess_algorithm("  E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855\n"),
+            Some("sha256")
+        );
+        assert_eq!(guess_algorithm(&"a".repeat(128)), Some("sha512"));
+    }
+
+    #[test]
+    fn test_guess_rejects_non_hex() {
+        assert_eq!(guess_algorithm("zz1d8cd98f00b204e9800998ecf8427e"), None);
+        assert_eq!(guess_algorithm(""), None);
+    }
+
+    #[test]
+    fn test_guess_unknown_length() {
+        assert_eq!(guess_algorithm("deadbeef"), None);
+        assert_eq!(guess_algorithm(&"a".repeat(96)), None);
+    }
+
+    #[tokio::test]
+    async fn test_detect_hash_type_command() {
+        assert_eq!(
+            detect_hash_type("9e107d9d372bb6826bd81d3542a419d6".to_string()).await,
+            Some("md5".to_string())
+        );
+        assert_eq!(detect_hash_type("not a hash".to_string()).await, None);
+    }
+}
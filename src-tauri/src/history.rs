@@ -0,0 +1,206 @@
+use crate::HashResult;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+const HISTORY_FILE: &str = "history.json";
+
+/// Number of results kept before the oldest are dropped.
+pub const DEFAULT_CAPACITY: usize = 50;
+
+/// Recent results from `calculate_checksum`, newest last. When created with
+/// [`History::load`] every change is written back to that file so the list
+/// survives restarts.
+pub struct History {
+    entries: Mutex<VecDeque<HashResult>>,
+    capacity: usize,
+    file: Option<PathBuf>,
+}
+
+impl Default for History {
+    fn default() -> History {
+        History::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl History {
+    /// An empty in-memory history holding at most `capacity` results.
+    pub fn new(capacity: usize) -> History {
+        History {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            file: None,
+        }
+    }
+
+    /// Restores the history saved at `path` and keeps saving to it. A
+    /// missing or unreadable file starts an empty history.
+    pub fn load(path: PathBuf) -> History {
+        let mut history = History::default();
+        let saved: Vec<HashResult> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let entries = history.entries.get_mut().unwrap();
+        for result in saved {
+            push_capped(entries, result, history.capacity);
+        }
+        history.file = Some(path);
+        history
+    }
+
+    pub fn push(&self, result: HashResult) {
+        let mut entries = self.entries.lock().unwrap();
+        push_capped(&mut entries, result, self.capacity);
+        self.save(&entries);
+    }
+
+    /// The stored results, oldest first.
+    pub fn entries(&self) -> Vec<HashResult> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clear();
+        self.save(&entries);
+    }
+
+    /// Writes `entries` to the history file, if there is one. Failing to
+    /// save only costs the history on the next launch, so it isn't fatal.
+    fn save(&self, entries: &VecDeque<HashResult>) {
+        let Some(path) = &self.file else {
+            return;
+        };
+        if let Err(e) = write_entries(path, entries) {
+            eprintln!("checksum-check: {}", e);
+        }
+    }
+}
+
+fn push_capped(entries: &mut VecDeque<HashResult>, result: HashResult, capacity: usize) {
+    while entries.len() >= capacity.max(1) {
+        entries.pop_front();
+    }
+    entries.push_back(result);
+}
+
+fn write_entries(path: &Path, entries: &VecDeque<HashResult>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string(entries).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+pub fn history_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(HISTORY_FILE))
+        .map_err(|e| e.to_string())
+}
+
+/// Recently hashed files, oldest first.
+#[tauri::command]
+pub fn get_history(history: State<'_, History>) -> Vec<HashResult> {
+    history.entries()
+}
+
+#[tauri::command]
+pub fn clear_history(history: State<'_, History>) {
+    history.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn result(path: &str) -> HashResult {
+        HashResult {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn paths(history: &History) -> Vec<String> {
+        history.entries().into_iter().map(|r| r.path).collect()
+    }
+
+    #[test]
+    fn test_history_evicts_oldest() {
+        let history = History::new(3);
+        for path in ["a", "b", "c", "d", "e"] {
+            history.push(result(path));
+        }
+        assert_eq!(paths(&history), vec!["c", "d", "e"]);
+    }
+
+    #[test]
+    fn test_history_default_capacity() {
+        let history = History::default();
+        for i in 0..DEFAULT_CAPACITY + 5 {
+            history.push(result(&i.to_string()));
+        }
+        let entries = paths(&history);
+        assert_eq!(entries.len(), DEFAULT_CAPACITY);
+        assert_eq!(entries[0], "5");
+    }
+
+    #[test]
+    fn test_clear_history_empties_it() {
+        let history = History::new(3);
+        history.push(result("a"));
+        history.clear();
+        assert!(history.entries().is_empty());
+    }
+
+    #[test]
+    fn test_history_persists_across_loads() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data").join(HISTORY_FILE);
+
+        let history = History::load(path.clone());
+        assert!(history.entries().is_empty());
+        history.push(result("a"));
+        history.push(result("b"));
+        assert_eq!(paths(&History::load(path.clone())), vec!["a", "b"]);
+
+        history.clear();
+        assert!(History::load(path).entries().is_empty());
+    }
+
+    #[test]
+    fn test_history_loads_records_from_older_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(HISTORY_FILE);
+        // Written before the legacy digests and empty-file flags existed
+        fs::write(
+            &path,
+            r#"[{"path":"fox.txt","md5":"9e107d9d372bb6826bd81d3542a419d6","file_size":43}]"#,
+        )
+        .unwrap();
+
+        let entries = History::load(path).entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "fox.txt");
+        assert_eq!(entries[0].md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(entries[0].file_size, 43);
+        assert!(entries[0].tiger.is_empty());
+        assert!(!entries[0].is_empty);
+    }
+
+    #[test]
+    fn test_history_commands() {
+        let app = tauri::test::mock_app();
+        app.manage(History::default());
+        app.state::<History>().push(result("a"));
+
+        assert_eq!(get_history(app.state()).len(), 1);
+        clear_history(app.state());
+        assert!(get_history(app.state()).is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::algorithms::{self, Algorithm};
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use siphasher::sip::SipHasher13;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{self, Read};
+
+fn mac_reader<M: Mac + KeyInit, R: Read>(reader: R, key: &[u8]) -> io::Result<Vec<u8>> {
+    let mut mac = <M as KeyInit>::new_from_slice(key)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    algorithms::for_each_chunk(reader, |chunk| mac.update(chunk))?;
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// Computes an HMAC over everything readable from `reader`.
+fn hmac_reader<R: Read>(reader: R, key: &[u8], algorithm: Algorithm) -> Result<String, String> {
+    let digest = match algorithm {
+        Algorithm::Md5 => mac_reader::<Hmac<Md5>, R>(reader, key),
+        Algorithm::Sha1 => mac_reader::<Hmac<Sha1>, R>(reader, key),
+        Algorithm::Sha256 => mac_reader::<Hmac<Sha256>, R>(reader, key),
+        Algorithm::Sha512 => mac_reader::<Hmac<Sha512>, R>(reader, key),
+        other => return Err(format!("HMAC is not supported for {}", other.id())),
+    };
+    digest
+        .map(|bytes| algorithms::hex_lower(&bytes))
+        .map_err(|e| e.to_string())
+}
+
+/// Returns the lowercase hex HMAC of the file, keyed by the UTF-8 bytes of
+/// `key`. Supports md5, sha1, sha256 and sha512.
+#[tauri::command]
+pub async fn calculate_hmac(
+    path: String,
+    key: String,
+    algorithm: String,
+) -> Result<String, String> {
+    let algorithm = Algorithm::from_id(&algorithm)
+        .ok_or_else(|| format!("unsupported algorithm: {}", algorithm))?;
+    let file = File::open(&path).map_err(|e| e.to_string())?;
+    hmac_reader(file, key.as_bytes(), algorithm)
+}
+
+/// Computes SipHash-1-3 keyed by `(key0, key1)` over everything readable
+/// from `reader`, as 16 hex digits.
+fn siphash_reader<R: Read>(reader: R, key0: u64, key1: u64) -> io::Result<String> {
+    let mut hasher = SipHasher13::new_with_keys(key0, key1);
+    algorithms::for_each_chunk(reader, |chunk| hasher.write(chunk))?;
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+/// Returns a 64-bit SipHash-1-3 of the file keyed by two secret words.
+/// Not a cryptographic digest, but an attacker who doesn't know the key
+/// can't craft colliding files, so it suits keyed dedup buckets.
+#[tauri::command]
+pub async fn calculate_siphash(path: String, key0: u64, key1: u64) -> Result<String, String> {
+    let file = File::open(&path).map_err(|e| e.to_string())?;
+    siphash_reader(file, key0, key1).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_file(content: &[u8]) -> (NamedTempFile, String) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        (file, path)
+    }
+
+    #[tokio::test]
+    async fn test_rfc4231_case_1() {
+        let (_file, path) = create_test_file(b"Hi There");
+        let key = "\x0b".repeat(20);
+
+        let result = calculate_hmac(path, key, "sha256".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rfc4231_case_2() {
+        let (_file, path) = create_test_file(b"what do ya want for nothing?");
+
+        let sha256 = calculate_hmac(path.clone(), "Jefe".to_string(), "sha256".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            sha256,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let sha512 = calculate_hmac(path, "Jefe".to_string(), "sha512".to_string())
+            .await
+            .unwrap();
+        assert_eq!(sha512, "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_algorithm() {
+        let (_file, path) = create_test_file(b"data");
+
+        assert!(calculate_hmac(path.clone(), "k".into(), "blake3".into())
+            .await
+            .is_err());
+        assert!(calculate_hmac(path, "k".into(), "nope".into())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_siphash_depends_on_key() {
+        let (_file, path) = create_test_file(b"The quick brown fox jumps over the lazy dog");
+
+        let first = calculate_siphash(path.clone(), 1, 2).await.unwrap();
+        let again = calculate_siphash(path.clone(), 1, 2).await.unwrap();
+        let other = calculate_siphash(path, 2, 1).await.unwrap();
+
+        assert_eq!(first.len(), 16);
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_siphash_is_chunk_independent() {
+        let content: Vec<u8> = (0..200_000u32).map(|i| (i % 253) as u8).collect();
+        let mut whole = SipHasher13::new_with_keys(7, 9);
+        whole.write(&content);
+
+        assert_eq!(
+            siphash_reader(content.as_slice(), 7, 9).unwrap(),
+            format!("{:016x}", whole.finish())
+        );
+    }
+}
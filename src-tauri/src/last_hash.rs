@@ -0,0 +1,177 @@
+use crate::encoding::truncate_middle;
+use crate::HashResult;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Id of the tray icon built in `main`, used to look it up again later.
+pub const TRAY_ID: &str = "main";
+
+const DEFAULT_TOOLTIP: &str = "Checksum Check";
+
+/// Characters of the digest shown in the tooltip, counting the `…`.
+const TOOLTIP_DIGEST_LEN: usize = 17;
+
+/// Longer file names are shortened in the middle, keeping the extension.
+const TOOLTIP_NAME_LEN: usize = 32;
+
+/// Longest error message shown in the tooltip.
+const TOOLTIP_ERROR_LEN: usize = 48;
+
+/// The most recent result from `calculate_checksum`.
+#[derive(Default)]
+pub struct LastHash(Mutex<Option<HashResult>>);
+
+/// Tray tooltip for `result`, e.g. `sha256: d7a8fbb3…37c9e592 (fox.txt)`,
+/// or the app name when nothing has been hashed yet. Falls back to MD5 when
+/// SHA-256 wasn't requested.
+pub fn tooltip(result: Option<&HashResult>) -> String {
+    let Some(result) = result else {
+        return DEFAULT_TOOLTIP.to_string();
+    };
+    let (name, digest) = if result.sha256.is_empty() {
+        ("md5", &result.md5)
+    } else {
+        ("sha256", &result.sha256)
+    };
+    if digest.is_empty() {
+        return DEFAULT_TOOLTIP.to_string();
+    }
+    format!(
+        "{}: {} ({})",
+        name,
+        truncate_middle(digest, TOOLTIP_DIGEST_LEN),
+        file_name(&result.path)
+    )
+}
+
+/// Tray tooltip after re-hashing `path` failed, e.g.
+/// `Re-hash of fox.txt failed: file not found`.
+fn failure_tooltip(path: &str, error: &str) -> String {
+    format!(
+        "Re-hash of {} failed: {}",
+        file_name(path),
+        truncate_middle(error, TOOLTIP_ERROR_LEN)
+    )
+}
+
+/// Last component of `path`, shortened to fit the tooltip.
+fn file_name(path: &str) -> String {
+    let name = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    truncate_middle(&name, TOOLTIP_NAME_LEN)
+}
+
+/// Stores `result` as the latest hash and shows it in the tray tooltip.
+pub fn record<R: Runtime>(app: &AppHandle<R>, result: &HashResult) {
+    *app.state::<LastHash>().0.lock().unwrap() = Some(result.clone());
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(tooltip(Some(result))));
+    }
+}
+
+/// Shows in the tray tooltip that re-hashing `path` failed, since a hash
+/// started from the tray has no window to report to. The last good result
+/// is kept.
+pub fn record_failure<R: Runtime>(app: &AppHandle<R>, path: &str, error: &str) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(failure_tooltip(path, error)));
+    }
+}
+
+/// Path of the latest result, if it came from a file that can be read
+/// again (not stdin).
+pub fn last_path<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    app.state::<LastHash>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|result| result.path.clone())
+        .filter(|path| !path.is_empty() && path != crate::STDIN_PATH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tooltip_without_result() {
+        assert_eq!(tooltip(None), "Checksum Check");
+    }
+
+    #[test]
+    fn test_tooltip_shows_sha256_prefix() {
+        let result = HashResult {
+            path: "/downloads/fox.txt".to_string(),
+            md5: "9e107d9d372bb6826bd81d3542a419d6".to_string(),
+            sha256: "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tooltip(Some(&result)),
+            "sha256: d7a8fbb3…37c9e592 (fox.txt)"
+        );
+
+        let md5_only = HashResult {
+            sha256: String::new(),
+            ..result
+        };
+        assert_eq!(tooltip(Some(&md5_only)), "md5: 9e107d9d…42a419d6 (fox.txt)");
+    }
+
+    #[test]
+    fn test_tooltip_truncates_long_file_name() {
+        let result = HashResult {
+            path: format!("/downloads/{}.iso", "x".repeat(100)),
+            sha256: "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592".to_string(),
+            ..Default::default()
+        };
+        let tooltip = tooltip(Some(&result));
+        assert!(tooltip.ends_with("xxxx.iso)"));
+        assert!(tooltip.chars().count() < 70);
+    }
+
+    #[test]
+    fn test_failure_tooltip() {
+        assert_eq!(
+            failure_tooltip("/downloads/fox.txt", "file not found"),
+            "Re-hash of fox.txt failed: file not found"
+        );
+        let long = failure_tooltip("/downloads/fox.txt", &"e".repeat(200));
+        assert!(long.chars().count() < 100);
+    }
+
+    #[test]
+    fn test_record_keeps_latest_result() {
+        let app = tauri::test::mock_app();
+        app.manage(LastHash::default());
+        let result = HashResult {
+            path: "a.bin".to_string(),
+            ..Default::default()
+        };
+
+        record(app.handle(), &result);
+        assert_eq!(*app.state::<LastHash>().0.lock().unwrap(), Some(result));
+    }
+
+    #[test]
+    fn test_last_path_skips_stdin() {
+        let app = tauri::test::mock_app();
+        app.manage(LastHash::default());
+        assert_eq!(last_path(app.handle()), None);
+
+        let mut result = HashResult {
+            path: "/downloads/fox.txt".to_string(),
+            ..Default::default()
+        };
+        record(app.handle(), &result);
+        assert_eq!(last_path(app.handle()), Some("/downloads/fox.txt".into()));
+
+        result.path = crate::STDIN_PATH.to_string();
+        record(app.handle(), &result);
+        assert_eq!(last_path(app.handle()), None);
+    }
+}
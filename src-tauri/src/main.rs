@@ -1,122 +1,1004 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use md5::Md5;
-use sha1::Sha1;
-use sha2::{Digest, Sha256, Sha512};
+mod algorithms;
+mod archive;
+mod audit;
+mod blake2_var;
+mod cache;
+mod chunking;
+mod cli;
+mod clipboard;
+mod compare;
+mod directory;
+mod encoding;
+mod error;
+mod export;
+mod hash_type;
+mod history;
+mod keyed;
+mod last_hash;
+mod manifest;
+mod multi_hasher;
+mod normalize;
+mod notify;
+mod operations;
+mod paths;
+mod quick;
+mod remote;
+mod self_test;
+mod settings;
+mod store;
+mod text_encoding;
+mod tree_hash;
+mod verify;
+mod watch;
+
+use algorithms::Algorithm;
+use cache::{FileStamp, HashCache};
+use chrono::{DateTime, SecondsFormat, Utc};
+use error::ChecksumError;
+use flate2::read::GzDecoder;
+use history::History;
+use last_hash::LastHash;
+use multi_hasher::MultiHasher;
+use normalize::TextNormalizer;
+use operations::Operations;
+use settings::WindowPosition;
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, IsTerminal, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{
+    ipc::Channel,
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WindowEvent,
+    AppHandle, Manager, RunEvent, Runtime, State, WebviewWindow, WindowEvent,
 };
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
 
-#[derive(serde::Serialize)]
+/// Digests for algorithms that weren't requested are left as empty strings.
+///
+/// Fields missing when deserializing take their defaults, so history files
+/// and exports written by older versions still load.
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(default)]
 struct HashResult {
+    /// The path as given, or relative to the root for directory results.
+    path: String,
+    /// Last component of `path`, or the whole path when it has none (`/`).
+    file_name: String,
+    /// `path` without its last component, empty for a bare file name.
+    parent_dir: String,
     md5: String,
     sha1: String,
+    sha224: String,
     sha256: String,
+    sha384: String,
     sha512: String,
+    blake3: String,
+    sha3_256: String,
+    sha3_512: String,
+    xxh3: String,
+    adler32: String,
+    /// Opt-in legacy and regional digests, only filled when explicitly
+    /// selected.
+    ripemd160: String,
+    whirlpool: String,
+    tiger: String,
+    gost94: String,
+    /// First 8 bytes of the SHA-256 as `d7a8 fbb3 07d7 8094`, for comparing
+    /// by eye or reading aloud. Empty when SHA-256 wasn't computed.
+    fingerprint: String,
+    /// Algorithms that failed, by id, with the reason. Their digest fields
+    /// stay empty while the others are still filled in.
+    errors: BTreeMap<String, String>,
+    /// Bytes actually read and hashed.
     file_size: u64,
-    modified: String,
-    created: String,
+    /// Size reported by the filesystem before hashing. Differs from
+    /// `file_size` when the file was being written to at the time.
+    metadata_size: u64,
+    /// Unix seconds, or `None` when the filesystem doesn't record the time
+    /// (creation time is missing on many Linux setups).
+    modified: Option<u64>,
+    created: Option<u64>,
+    /// RFC 3339 UTC forms of `modified`/`created`, e.g. `2024-01-15T12:34:56Z`,
+    /// empty when unavailable.
+    modified_iso: String,
+    created_iso: String,
+    /// True when `created` is more than a second after `modified`, as
+    /// happens after copying between machines with skewed clocks.
+    timestamps_suspect: bool,
+    /// True when no bytes were read. Every empty file has the same digests,
+    /// and in a batch one usually means a truncated download.
+    is_empty: bool,
+    /// True for zero-byte files or files containing only whitespace,
+    /// which usually means a build step left a placeholder behind.
+    is_empty_or_placeholder: bool,
+    /// Sniffed from the leading bytes, `application/octet-stream` if unknown.
+    mime_type: String,
+    /// Wall-clock time spent reading and hashing.
+    duration_ms: u64,
+    /// Megabytes (10^6 bytes) hashed per second, or 0.0 when too quick to
+    /// measure.
+    throughput_mbps: f64,
+    readonly: bool,
+    /// Unix permission bits such as `0o644`; always 0 on Windows.
+    mode: u32,
 }
 
-fn calculate_file_hash(path: &str) -> io::Result<HashResult> {
-    let mut file = File::open(path)?;
-    let metadata = file.metadata()?;
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)?;
-
-    // Get file metadata
-    let file_size = metadata.len();
-    let modified = metadata
-        .modified()?
-        .duration_since(std::time::UNIX_EPOCH)
+/// Seconds since the Unix epoch, or 0 for times before it.
+fn epoch_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
-        .unwrap_or(0);
-    let created = metadata
-        .created()?
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
-
-    // Calculate MD5
-    let mut md5_hasher = Md5::new();
-    md5_hasher.update(&buffer);
-    let md5_result = md5_hasher.finalize();
-    let md5_hex = format!("{:x}", md5_result);
-
-    // Calculate SHA1
-    let mut sha1_hasher = Sha1::new();
-    sha1_hasher.update(&buffer);
-    let sha1_result = sha1_hasher.finalize();
-    let sha1_hex = format!("{:x}", sha1_result);
-
-    // Calculate SHA256
-    let mut sha256_hasher = Sha256::new();
-    sha256_hasher.update(&buffer);
-    let sha256_result = sha256_hasher.finalize();
-    let sha256_hex = format!("{:x}", sha256_result);
-
-    // Calculate SHA512
-    let mut sha512_hasher = Sha512::new();
-    sha512_hasher.update(&buffer);
-    let sha512_result = sha512_hasher.finalize();
-    let sha512_hex = format!("{:x}", sha512_result);
-
-    Ok(HashResult {
-        md5: md5_hex,
-        sha1: sha1_hex,
-        sha256: sha256_hex,
-        sha512: sha512_hex,
-        file_size,
-        modified: modified.to_string(),
-        created: created.to_string(),
-    })
+        .unwrap_or(0)
+}
+
+fn iso8601_utc(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Hashing speed in MB/s, guarding against a zero duration on tiny files.
+fn throughput_mbps(bytes: u64, elapsed: Duration) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds > 0.0 {
+        bytes as f64 / 1_000_000.0 / seconds
+    } else {
+        0.0
+    }
+}
+
+/// Groups the first 16 hex characters of `sha256` in fours.
+fn fingerprint(sha256: &str) -> String {
+    sha256
+        .as_bytes()
+        .chunks(4)
+        .take(4)
+        .map(|group| String::from_utf8_lossy(group))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+const UNKNOWN_MIME_TYPE: &str = "application/octet-stream";
+
+fn sniff_mime_type(head: &[u8]) -> String {
+    infer::get(head)
+        .map(|kind| kind.mime_type())
+        .unwrap_or(UNKNOWN_MIME_TYPE)
+        .to_string()
+}
+
+#[cfg(unix)]
+fn permission_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn permission_mode(_metadata: &std::fs::Metadata) -> u32 {
+    0
+}
+
+fn is_placeholder_content(content: &[u8]) -> bool {
+    content.iter().all(|b| b.is_ascii_whitespace())
+}
+
+impl HashResult {
+    fn digest_mut(&mut self, algorithm: Algorithm) -> &mut String {
+        match algorithm {
+            Algorithm::Md5 => &mut self.md5,
+            Algorithm::Sha1 => &mut self.sha1,
+            Algorithm::Sha224 => &mut self.sha224,
+            Algorithm::Sha256 => &mut self.sha256,
+            Algorithm::Sha384 => &mut self.sha384,
+            Algorithm::Sha512 => &mut self.sha512,
+            Algorithm::Blake3 => &mut self.blake3,
+            Algorithm::Sha3_256 => &mut self.sha3_256,
+            Algorithm::Sha3_512 => &mut self.sha3_512,
+            Algorithm::Xxh3 => &mut self.xxh3,
+            Algorithm::Adler32 => &mut self.adler32,
+            Algorithm::Ripemd160 => &mut self.ripemd160,
+            Algorithm::Whirlpool => &mut self.whirlpool,
+            Algorithm::Tiger => &mut self.tiger,
+            Algorithm::Gost94 => &mut self.gost94,
+        }
+    }
+
+    /// Sets `path` along with the `file_name` and `parent_dir` split from it.
+    fn set_path(&mut self, path: &str) {
+        let parts = Path::new(path);
+        self.path = path.to_string();
+        self.file_name = parts.file_name().map_or_else(
+            || path.to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+        self.parent_dir = parts
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .unwrap_or_default();
+    }
+
+    /// Rewrites every digest in uppercase hex, as some vendors publish them.
+    fn uppercase_digests(&mut self) {
+        for algorithm in Algorithm::ALL {
+            self.digest_mut(algorithm).make_ascii_uppercase();
+        }
+        self.fingerprint.make_ascii_uppercase();
+    }
+}
+
+/// Minimum number of bytes between progress reports, so large files don't
+/// flood the IPC channel.
+const PROGRESS_INTERVAL: u64 = 4 * 1024 * 1024;
+
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq)]
+struct ProgressPayload {
+    bytes_done: u64,
+    total: u64,
+}
+
+/// Hashes the file with only the given algorithms.
+fn calculate_file_hash(path: &str, algorithms: &[Algorithm]) -> io::Result<HashResult> {
+    calculate_file_hash_with_progress(path, algorithms, None, |_| {}, None)
 }
 
+/// Like [`calculate_file_hash`], calling `on_progress` at most every
+/// [`PROGRESS_INTERVAL`] bytes and once more when the file is done.
+///
+/// When `cancel` is set the hash stops at the next chunk boundary with a
+/// "cancelled" error.
+fn calculate_file_hash_with_progress(
+    path: &str,
+    algorithms: &[Algorithm],
+    buffer_size: Option<usize>,
+    on_progress: impl FnMut(ProgressPayload),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<HashResult> {
+    if path == STDIN_PATH {
+        let stdin = io::stdin();
+        // Nothing was piped in, so reading would just wait on the keyboard
+        if stdin.is_terminal() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no input piped on stdin",
+            ));
+        }
+        return hash_piped(stdin.lock(), algorithms, buffer_size, on_progress, cancel);
+    }
+    let path = &paths::canonical_path(path)?;
+    let (file, metadata) = open_for_hashing(path)?;
+
+    let mut result = hash_content(
+        file,
+        metadata.len(),
+        algorithms,
+        buffer_size,
+        on_progress,
+        cancel,
+    )?;
+    fill_file_details(&mut result, path, &metadata);
+    Ok(result)
+}
+
+/// Hashes the file's content without stat'ing it, for network shares where
+/// metadata is slow or unreliable. `file_size` is the number of bytes read;
+/// timestamps, mode and `metadata_size` are left empty, and the path is
+/// used as given rather than canonicalized.
+fn calculate_content_hash(
+    path: &str,
+    algorithms: &[Algorithm],
+    buffer_size: Option<usize>,
+    cancel: Option<&AtomicBool>,
+) -> io::Result<HashResult> {
+    let file = File::open(paths::platform_path(path))?;
+    let mut result = hash_content(file, 0, algorithms, buffer_size, |_| {}, cancel)?;
+    result.set_path(path);
+    Ok(result)
+}
+
+/// Stores each digest in its field, or records the failure in `errors` so
+/// one broken algorithm doesn't void the others.
+fn apply_digests(
+    result: &mut HashResult,
+    algorithms: &[Algorithm],
+    digests: Vec<Result<Vec<u8>, String>>,
+) {
+    for (&algorithm, digest) in algorithms.iter().zip(digests) {
+        match digest {
+            Ok(digest) => *result.digest_mut(algorithm) = algorithms::hex_lower(&digest),
+            Err(e) => {
+                result.errors.insert(algorithm.id().to_string(), e);
+            }
+        }
+    }
+}
+
+/// Path that means "read from stdin", as in `somecommand | checksum-check -`.
+const STDIN_PATH: &str = "-";
+
+/// Hashes piped input. The size isn't known up front, so progress reports
+/// a total of 0 and timestamps are left empty.
+fn hash_piped<R: Read>(
+    reader: R,
+    algorithms: &[Algorithm],
+    buffer_size: Option<usize>,
+    on_progress: impl FnMut(ProgressPayload),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<HashResult> {
+    let mut result = hash_content(reader, 0, algorithms, buffer_size, on_progress, cancel)?;
+    result.set_path(STDIN_PATH);
+    Ok(result)
+}
+
+/// Opens `path` for hashing, refusing directories up front so the caller
+/// gets "path is a directory" instead of an OS read error.
+///
+/// Zero-length special files are refused too: `/proc` entries, character
+/// devices and FIFOs report size 0 but can stream forever (or block on
+/// open), so they are never read.
+fn open_for_hashing(path: &str) -> io::Result<(File, std::fs::Metadata)> {
+    let path = paths::platform_path(path);
+    let metadata = std::fs::metadata(&path)?;
+    if metadata.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::IsADirectory,
+            "path is a directory",
+        ));
+    }
+    if metadata.len() == 0 && !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "not a regular file",
+        ));
+    }
+    let file = File::open(&path)?;
+    let metadata = file.metadata()?;
+    Ok((file, metadata))
+}
+
+/// Converts a metadata timestamp to epoch seconds and RFC 3339, or
+/// `(None, "")` when the platform couldn't provide it.
+fn timestamp_fields(time: io::Result<SystemTime>) -> (Option<u64>, String) {
+    match time {
+        Ok(time) => (Some(epoch_seconds(time)), iso8601_utc(time)),
+        Err(_) => (None, String::new()),
+    }
+}
+
+/// Whether the creation time is later than the last write, allowing a
+/// second for filesystems that round the two differently.
+fn timestamps_suspect(created: Option<u64>, modified: Option<u64>) -> bool {
+    match (created, modified) {
+        (Some(created), Some(modified)) => created > modified.saturating_add(1),
+        _ => false,
+    }
+}
+
+/// Sets the path, timestamp and permission fields from the file's metadata.
+fn fill_file_details(result: &mut HashResult, path: &str, metadata: &std::fs::Metadata) {
+    result.set_path(path);
+    (result.modified, result.modified_iso) = timestamp_fields(metadata.modified());
+    (result.created, result.created_iso) = timestamp_fields(metadata.created());
+    result.timestamps_suspect = timestamps_suspect(result.created, result.modified);
+    result.readonly = metadata.permissions().readonly();
+    result.mode = permission_mode(metadata);
+}
+
+/// Hashes exactly `length` bytes starting at `offset`.
+/// `file_size` in the result is the hashed length.
+fn calculate_range_hash(
+    path: &str,
+    offset: u64,
+    length: u64,
+    algorithms: &[Algorithm],
+    buffer_size: Option<usize>,
+    cancel: Option<&AtomicBool>,
+) -> io::Result<HashResult> {
+    let path = &paths::canonical_path(path)?;
+    let (mut file, metadata) = open_for_hashing(path)?;
+    if offset
+        .checked_add(length)
+        .is_none_or(|end| end > metadata.len())
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "range {}..{} exceeds file size {}",
+                offset,
+                offset.saturating_add(length),
+                metadata.len()
+            ),
+        ));
+    }
+
+    file.seek(SeekFrom::Start(offset))?;
+    let mut result = hash_content(
+        file.take(length),
+        length,
+        algorithms,
+        buffer_size,
+        |_| {},
+        cancel,
+    )?;
+    fill_file_details(&mut result, path, &metadata);
+    Ok(result)
+}
+
+/// Hashes the decompressed contents of a gzip file, so a `.gz` can be
+/// checked against the digest of the original. `file_size` is the
+/// decompressed length; path and timestamps are those of the `.gz`.
 #[tauri::command]
-async fn calculate_checksum(path: String) -> Result<HashResult, String> {
-    calculate_file_hash(&path).map_err(|e| e.to_string())
+async fn calculate_checksum_gzip(path: String) -> Result<HashResult, String> {
+    calculate_gzip_hash(&path)
+}
+
+fn calculate_gzip_hash(path: &str) -> Result<HashResult, String> {
+    let path = &paths::canonical_path(path).map_err(|e| e.to_string())?;
+    let (file, metadata) = open_for_hashing(path).map_err(|e| e.to_string())?;
+    // The decompressed size isn't known up front, as with stdin
+    let mut result = hash_content(
+        GzDecoder::new(file),
+        0,
+        &Algorithm::DEFAULT,
+        None,
+        |_| {},
+        None,
+    )
+    .map_err(|e| format!("{}: {}", path, e))?;
+    fill_file_details(&mut result, path, &metadata);
+    Ok(result)
+}
+
+/// Hashes a text file as if it had no BOM and only LF line endings, so the
+/// same text saved on Windows and Linux gets the same digests.
+/// `file_size` is the normalized length.
+#[tauri::command]
+async fn calculate_checksum_normalized(path: String) -> Result<HashResult, String> {
+    calculate_normalized_hash(&path)
+}
+
+fn calculate_normalized_hash(path: &str) -> Result<HashResult, String> {
+    let path = &paths::canonical_path(path).map_err(|e| e.to_string())?;
+    let (file, metadata) = open_for_hashing(path).map_err(|e| e.to_string())?;
+    let mut result = hash_content(
+        TextNormalizer::new(file),
+        metadata.len(),
+        &Algorithm::DEFAULT,
+        None,
+        |_| {},
+        None,
+    )
+    .map_err(|e| format!("{}: {}", path, e))?;
+    fill_file_details(&mut result, path, &metadata);
+    Ok(result)
+}
+
+/// Streams `reader` through the selected hashers and returns a result with
+/// the digests, the placeholder flag and `file_size` set to the bytes read.
+/// `total` is the expected size, kept as `metadata_size` and used for
+/// progress. Path and timestamps are left for the caller.
+fn hash_content<R: Read>(
+    reader: R,
+    total: u64,
+    algorithms: &[Algorithm],
+    buffer_size: Option<usize>,
+    mut on_progress: impl FnMut(ProgressPayload),
+    cancel: Option<&AtomicBool>,
+) -> io::Result<HashResult> {
+    let mut hasher = MultiHasher::with_algorithms(algorithms);
+    let mut last_reported = 0u64;
+
+    // Feed every hasher from a fixed-size buffer so memory use stays flat
+    // regardless of file size; each digest runs on its own thread
+    algorithms::try_for_each_chunk_sized(reader, algorithms::buffer_size(buffer_size), |chunk| {
+        operations::check_cancelled(cancel)?;
+        hasher.update(chunk);
+        let bytes_done = hasher.bytes();
+        if bytes_done - last_reported >= PROGRESS_INTERVAL {
+            last_reported = bytes_done;
+            on_progress(ProgressPayload { bytes_done, total });
+        }
+        Ok(())
+    })?;
+    on_progress(ProgressPayload {
+        bytes_done: hasher.bytes(),
+        total,
+    });
+
+    let mut result = hasher.finalize();
+    result.metadata_size = total;
+    Ok(result)
+}
+
+/// Options for [`calculate_checksum`], sent from the frontend as
+/// `{ request: { path, ... } }`. Everything but `path` may be omitted, so
+/// new options can be added without breaking existing callers.
+#[derive(serde::Deserialize, Default, Debug, Clone, PartialEq)]
+struct HashRequest {
+    /// File to hash, or `-` for stdin.
+    path: String,
+    /// Algorithm ids; the defaults when omitted or empty.
+    algorithms: Option<Vec<String>>,
+    /// Registers the hash so [`operations::cancel_checksum`] can stop it.
+    op_id: Option<String>,
+    /// Uppercase hex digests instead of lowercase.
+    uppercase: Option<bool>,
+    /// Refuse symlinks with [`ChecksumError::IsSymlink`] when `Some(false)`.
+    follow_symlinks: Option<bool>,
+    /// Hash afresh even if a cached result is still valid.
+    bypass_cache: Option<bool>,
+    /// Hash the content without stat'ing the file.
+    skip_metadata: Option<bool>,
+    /// `(offset, length)` to hash only part of the file.
+    range: Option<(u64, u64)>,
+    /// Read buffer size in bytes, clamped as in
+    /// [`calculate_checksum_with_buffer`].
+    buffer_size: Option<usize>,
+}
+
+impl HashRequest {
+    /// A request for `path` with every option left at its default.
+    fn new(path: impl Into<String>) -> Self {
+        HashRequest {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Computes the requested algorithms, or the defaults when `algorithms` is
+/// omitted or empty. A `path` of `-` hashes stdin instead of a file.
+///
+/// Passing an `op_id` lets [`operations::cancel_checksum`] stop the hash
+/// part-way through, and `uppercase` switches the digests to uppercase hex.
+///
+/// Symlinks are followed unless `follow_symlinks` is `Some(false)`, in which
+/// case a symlink is refused with [`ChecksumError::IsSymlink`] rather than
+/// silently hashing its target.
+///
+/// Hashing a file again returns the earlier result while its size and
+/// modification time are unchanged; `bypass_cache` forces a fresh hash.
+/// `skip_metadata` hashes the content without stat'ing the file (see
+/// [`calculate_content_hash`]), which also bypasses the cache.
+///
+/// A `range` hashes only those bytes, as [`calculate_checksum_range`] does;
+/// it needs the file's size, so `skip_metadata` is ignored, and the partial
+/// result is never cached.
+///
+/// The result is remembered as the latest hash, shown in the tray tooltip
+/// and added to the history.
+/// Slow hashes also raise a desktop notification while the window is hidden.
+#[tauri::command]
+async fn calculate_checksum<R: Runtime>(
+    request: HashRequest,
+    operations: State<'_, Operations>,
+    app: AppHandle<R>,
+) -> Result<HashResult, ChecksumError> {
+    let started = Instant::now();
+    let HashRequest {
+        path,
+        algorithms,
+        op_id,
+        uppercase,
+        follow_symlinks,
+        bypass_cache,
+        skip_metadata,
+        range,
+        buffer_size,
+    } = request;
+    paths::check_utf8(&path).map_err(ChecksumError::InvalidArgument)?;
+    let selected = algorithms::parse_selection(&algorithms.unwrap_or_default())
+        .map_err(ChecksumError::InvalidArgument)?;
+    if range.is_some() && path == STDIN_PATH {
+        return Err(ChecksumError::InvalidArgument(
+            "a range cannot be read from stdin".to_string(),
+        ));
+    }
+    if !follow_symlinks.unwrap_or(true) && path != STDIN_PATH {
+        let metadata = std::fs::symlink_metadata(&path)?;
+        if metadata.file_type().is_symlink() {
+            return Err(ChecksumError::IsSymlink);
+        }
+    }
+    let skip_metadata = skip_metadata.unwrap_or(false) && path != STDIN_PATH && range.is_none();
+    let stamp = (path != STDIN_PATH && !skip_metadata && range.is_none())
+        .then(|| FileStamp::of(&path))
+        .flatten();
+    let cache = app.state::<HashCache>();
+    let cached = stamp
+        .filter(|_| !bypass_cache.unwrap_or(false))
+        .and_then(|stamp| cache.get(&path, &selected, stamp));
+    let outcome = match cached {
+        Some(result) => Ok(result),
+        None => {
+            let guard = op_id.as_deref().map(|id| operations.register(id));
+            let cancel = guard.as_ref().map(|g| g.flag());
+            let outcome = if let Some((offset, length)) = range {
+                calculate_range_hash(&path, offset, length, &selected, buffer_size, cancel)
+            } else if skip_metadata {
+                calculate_content_hash(&path, &selected, buffer_size, cancel)
+            } else {
+                calculate_file_hash_with_progress(&path, &selected, buffer_size, |_| {}, cancel)
+            };
+            if let (Some(stamp), Ok(result)) = (stamp, &outcome) {
+                cache.insert(&path, &selected, stamp, result.clone());
+            }
+            outcome
+        }
+    };
+    let error = outcome.as_ref().err().map(|e| e as &dyn std::fmt::Display);
+    audit::record_hash(&path, &selected, started.elapsed(), error);
+    let mut result = outcome?;
+    if uppercase.unwrap_or(false) {
+        result.uppercase_digests();
+    }
+    last_hash::record(&app, &result);
+    app.state::<History>().push(result.clone());
+    notify::notify_if_slow(&app, &result, started.elapsed());
+    Ok(result)
+}
+
+/// Compatibility shim for frontends that still send a bare `{ path }`:
+/// hashes it with every option at its default, as `calculate_checksum` did
+/// before it took a [`HashRequest`].
+#[tauri::command]
+async fn calculate_checksum_path<R: Runtime>(
+    path: String,
+    operations: State<'_, Operations>,
+    app: AppHandle<R>,
+) -> Result<HashResult, ChecksumError> {
+    calculate_checksum(HashRequest::new(path), operations, app).await
+}
+
+/// Computes the default algorithms while streaming [`ProgressPayload`] updates to
+/// the frontend for rendering a progress bar.
+#[tauri::command]
+async fn calculate_checksum_with_progress(
+    path: String,
+    on_progress: Channel<ProgressPayload>,
+    op_id: Option<String>,
+    operations: State<'_, Operations>,
+) -> Result<HashResult, String> {
+    let guard = op_id.as_deref().map(|id| operations.register(id));
+    calculate_file_hash_with_progress(
+        &path,
+        &Algorithm::DEFAULT,
+        None,
+        |progress| {
+            // A closed channel just means nobody is watching any more
+            let _ = on_progress.send(progress);
+        },
+        guard.as_ref().map(|g| g.flag()),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Computes the default algorithms reading `buffer_size` bytes at a time, for
+/// tuning to the storage: larger buffers cut syscalls on fast NVMe, smaller
+/// ones suit slow network shares. Clamped to 4 KiB..=16 MiB, default 64 KiB.
+#[tauri::command]
+async fn calculate_checksum_with_buffer(
+    path: String,
+    buffer_size: Option<usize>,
+) -> Result<HashResult, String> {
+    calculate_file_hash_with_progress(&path, &Algorithm::DEFAULT, buffer_size, |_| {}, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Hashes a pasted string with the default algorithms, encoded as
+/// `encoding` first (utf-8, utf-16le, utf-16be, latin-1 or windows-1252;
+/// default utf-8).
+/// `file_size` is the encoded byte length; path and timestamps are empty.
+#[tauri::command]
+async fn calculate_checksum_text(
+    text: String,
+    encoding: Option<String>,
+) -> Result<HashResult, String> {
+    let bytes = text_encoding::encode(&text, encoding.as_deref().unwrap_or("utf-8"))?;
+    calculate_bytes_hash(&bytes)
+}
+
+fn calculate_text_hash(text: &str) -> Result<HashResult, String> {
+    calculate_bytes_hash(text.as_bytes())
+}
+
+/// Hashes bytes the webview already holds (a dropped or fetched file), so
+/// they don't have to be written to a temp file first. `file_size` is the
+/// byte length; path and timestamps are empty.
+#[tauri::command]
+async fn calculate_checksum_bytes(data: Vec<u8>) -> Result<HashResult, String> {
+    calculate_bytes_hash(&data)
+}
+
+fn calculate_bytes_hash(data: &[u8]) -> Result<HashResult, String> {
+    hash_content(
+        data,
+        data.len() as u64,
+        &Algorithm::DEFAULT,
+        None,
+        |_| {},
+        None,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Opens a multi-select file dialog and hashes every chosen file with the
+/// default algorithms, in the order picked. Cancelling the dialog returns
+/// no results rather than an error.
+#[tauri::command]
+async fn pick_and_hash<R: Runtime>(app: AppHandle<R>) -> Result<Vec<HashResult>, String> {
+    let Some(picked) = app.dialog().file().blocking_pick_files() else {
+        return Ok(Vec::new());
+    };
+    let paths = picked
+        .into_iter()
+        .map(|file| {
+            file.into_path()
+                .map(|path| path.to_string_lossy().to_string())
+                .map_err(|e| e.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    hash_paths(&paths)
+}
+
+/// Hashes every regular file matching `pattern`, e.g. `/downloads/*.iso`
+/// or `src/**/*.rs`, in sorted order. A pattern matching nothing returns
+/// no results; a malformed one is an error.
+#[tauri::command]
+async fn hash_glob(pattern: String) -> Result<Vec<HashResult>, String> {
+    let matches =
+        glob::glob(&pattern).map_err(|e| format!("invalid pattern {}: {}", pattern, e))?;
+    let mut paths = Vec::new();
+    for entry in matches {
+        let path = entry.map_err(|e| e.to_string())?;
+        if path.is_file() {
+            paths.push(path.to_string_lossy().to_string());
+        }
+    }
+    hash_paths(&paths)
+}
+
+/// Hashes each of `paths` with the default algorithms, failing on the first
+/// file that can't be read.
+fn hash_paths(paths: &[String]) -> Result<Vec<HashResult>, String> {
+    paths
+        .iter()
+        .map(|path| calculate_file_hash(path, &Algorithm::DEFAULT).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Hashes the `length` bytes at `offset`, e.g. a header or partition
+/// embedded in a larger image. Errors if the range runs past the end.
+#[tauri::command]
+async fn calculate_checksum_range(
+    path: String,
+    offset: u64,
+    length: u64,
+) -> Result<HashResult, String> {
+    calculate_range_hash(&path, offset, length, &Algorithm::DEFAULT, None, None)
+        .map_err(|e| e.to_string())
+}
+
+/// Checks whether a file is empty or whitespace-only without hashing it.
+/// Stops reading at the first non-whitespace byte.
+#[tauri::command]
+async fn detect_placeholder(path: String) -> Result<bool, String> {
+    let (mut file, _) = open_for_hashing(&path).map_err(|e| e.to_string())?;
+    let mut buffer = vec![0u8; algorithms::CHUNK_SIZE];
+    loop {
+        let n = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Ok(true);
+        }
+        if !is_placeholder_content(&buffer[..n]) {
+            return Ok(false);
+        }
+    }
+}
+
+/// Shows the main window if it's hidden and hides it otherwise.
+fn toggle_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            let _ = window.show();
+        }
+    }
+}
+
+/// Re-runs [`calculate_checksum`] on the last hashed file in the background
+/// when `tray_rehash_on_click` is enabled, refreshing the stored result and
+/// tooltip, or showing the failure in the tooltip. Returns false when the
+/// click should toggle the window instead.
+fn rehash_on_tray_click<R: Runtime>(app: &AppHandle<R>) -> bool {
+    let enabled = settings::settings_path(app)
+        .map(|path| settings::load(&path).tray_rehash_on_click)
+        .unwrap_or(false);
+    let Some(path) = enabled.then(|| last_hash::last_path(app)).flatten() else {
+        return false;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = calculate_checksum(HashRequest::new(&path), app.state(), app.clone()).await
+        {
+            last_hash::record_failure(&app, &path, &e.to_string());
+        }
+    });
+    true
+}
+
+/// Remembers where the window is so the next launch can put it back.
+fn save_window_position(window: &WebviewWindow) {
+    let (Ok(position), Ok(path)) = (
+        window.outer_position(),
+        settings::settings_path(window.app_handle()),
+    ) else {
+        return;
+    };
+    // Losing the position isn't worth interrupting the close for
+    let _ = settings::update(&path, |settings| {
+        settings.window_position = Some(WindowPosition {
+            x: position.x,
+            y: position.y,
+        });
+    });
+}
+
+/// Moves the window back to `saved`, clamped to the monitor that contains
+/// it or to the primary monitor if that one has been disconnected.
+/// Returns false when no monitor could be found.
+fn restore_window_position(window: &WebviewWindow, saved: WindowPosition) -> bool {
+    let contains = |monitor: &tauri::Monitor| {
+        let origin = monitor.position();
+        let size = monitor.size();
+        (origin.x..origin.x + size.width as i32).contains(&saved.x)
+            && (origin.y..origin.y + size.height as i32).contains(&saved.y)
+    };
+    let monitor = window
+        .available_monitors()
+        .unwrap_or_default()
+        .into_iter()
+        .find(contains)
+        .or_else(|| window.primary_monitor().ok().flatten());
+    let Some(monitor) = monitor else {
+        return false;
+    };
+
+    let window_size = window
+        .outer_size()
+        .map(|size| (size.width, size.height))
+        .unwrap_or_default();
+    let position = settings::clamp_to_monitor(
+        saved,
+        window_size,
+        (monitor.position().x, monitor.position().y),
+        (monitor.size().width, monitor.size().height),
+    );
+    window
+        .set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+            x: position.x,
+            y: position.y,
+        }))
+        .is_ok()
 }
 
 fn main() {
+    // Headless mode: hash and print without starting the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let parsed = cli::parse_args(&args);
+    if parsed.is_some() {
+        cli::attach_parent_console();
+    }
+    match parsed {
+        Some(Ok(cli_args)) => std::process::exit(cli::run(&cli_args)),
+        Some(Err(usage)) => {
+            eprintln!("checksum-check: {}", usage);
+            std::process::exit(2);
+        }
+        None => {}
+    }
+
     tauri::Builder::default()
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(Operations::default())
+        .manage(LastHash::default())
+        .manage(HashCache::default())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    if event.state == ShortcutState::Pressed {
+                        toggle_main_window(app);
+                    }
+                })
+                .build(),
+        )
         .setup(|app| {
+            let history = history::history_path(app.handle())
+                .map(History::load)
+                .unwrap_or_default();
+            app.manage(history);
+
+            let settings = settings::settings_path(app.handle())
+                .map(|path| settings::load(&path))
+                .unwrap_or_default();
+
+            if settings.audit_log {
+                let audit_log = app
+                    .path()
+                    .app_log_dir()
+                    .map_err(|e| e.to_string())
+                    .and_then(|dir| audit::init(&dir));
+                match audit_log {
+                    Ok(audit_log) => {
+                        app.manage(audit_log);
+                    }
+                    Err(e) => notify::notify_error(
+                        app.handle(),
+                        &format!("Cannot start the audit log: {}", e),
+                    ),
+                }
+            }
+
+            // A taken or malformed hotkey shouldn't stop the app starting
+            if let Err(e) = app.global_shortcut().register(settings.hotkey()) {
+                notify::notify_error(
+                    app.handle(),
+                    &format!("Cannot register {}: {}", settings.hotkey(), e),
+                );
+            }
+
             // Set up window close handler
             if let Some(window) = app.get_webview_window("main") {
                 let window_clone = window.clone();
                 window.on_window_event(move |event| {
                     if let WindowEvent::CloseRequested { api, .. } = event {
+                        save_window_position(&window_clone);
                         window_clone.hide().unwrap();
                         api.prevent_close();
                     }
                 });
             }
 
-            // Position and show the main window on launch
+            // Position the main window, and show it unless the user asked
+            // to start in the tray
             if let Some(window) = app.get_webview_window("main") {
+                let saved_position = settings.window_position;
+                let start_hidden = settings.start_hidden;
+                let window_offset = settings.window_offset_percent();
                 let window_clone = window.clone();
                 tauri::async_runtime::spawn(async move {
-                    // First center the window
-                    let _ = window_clone.center();
-                    // Then move it up by 15% of the screen height
-                    if let Some(monitor) = window_clone.current_monitor().ok().flatten() {
-                        if let Ok(position) = window_clone.outer_position() {
-                            let monitor_size = monitor.size();
-                            let offset_y = (monitor_size.height as f64 * 0.20) as i32;
-                            let new_position = tauri::Position::Physical(tauri::PhysicalPosition {
-                                x: position.x,
-                                y: position.y - offset_y,
-                            });
-                            let _ = window_clone.set_position(new_position);
+                    let restored = saved_position
+                        .is_some_and(|saved| restore_window_position(&window_clone, saved));
+                    // First run: center the window, then move it up by the
+                    // configured share of the screen height
+                    if !restored {
+                        let _ = window_clone.center();
+                        if let Some(monitor) = window_clone.current_monitor().ok().flatten() {
+                            if let Ok(position) = window_clone.outer_position() {
+                                let new_position =
+                                    tauri::Position::Physical(tauri::PhysicalPosition {
+                                        x: position.x,
+                                        y: settings::offset_window_y(
+                                            position.y,
+                                            monitor.position().y,
+                                            monitor.size().height,
+                                            window_offset,
+                                        ),
+                                    });
+                                let _ = window_clone.set_position(new_position);
+                            }
                         }
                     }
-                    let _ = window_clone.show();
-                    let _ = window_clone.set_focus();
+                    if !start_hidden {
+                        let _ = window_clone.show();
+                        let _ = window_clone.set_focus();
+                    }
                 });
             }
 
@@ -127,52 +1009,130 @@ fn main() {
             let menu = Menu::with_items(app, &[&quit_i])?;
 
             // Build the tray
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(last_hash::TRAY_ID)
                 .icon(app.default_window_icon().unwrap().clone())
+                .tooltip(last_hash::tooltip(None))
                 .menu(&menu)
-                .menu_on_left_click(false)
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "quit" => {
+                .show_menu_on_left_click(false)
+                .on_menu_event(|app, event| {
+                    if event.id.as_ref() == "quit" {
                         app.exit(0);
                     }
-                    _ => {}
                 })
-                .on_tray_icon_event(|tray, event| match event {
-                    TrayIconEvent::Click {
+                .on_tray_icon_event(|tray, event| {
+                    if let TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
                         ..
-                    } => {
+                    } = event
+                    {
                         let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            if window.is_visible().unwrap_or(false) {
-                                let _ = window.hide();
-                            } else {
-                                let _ = window.show();
-                            }
+                        if !rehash_on_tray_click(app) {
+                            toggle_main_window(app);
                         }
                     }
-                    _ => {}
                 })
                 .build(app)?;
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![calculate_checksum])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![
+            calculate_checksum,
+            calculate_checksum_path,
+            calculate_checksum_with_progress,
+            calculate_checksum_with_buffer,
+            calculate_checksum_text,
+            calculate_checksum_bytes,
+            pick_and_hash,
+            hash_glob,
+            calculate_checksum_range,
+            calculate_checksum_gzip,
+            calculate_checksum_normalized,
+            operations::cancel_checksum,
+            operations::cancel_all,
+            algorithms::supported_algorithms,
+            algorithms::hashing_capabilities,
+            algorithms::cpu_hash_features,
+            detect_placeholder,
+            tree_hash::calculate_tree_hash,
+            chunking::chunk_file,
+            archive::hash_zip_entry,
+            archive::hash_archive,
+            verify::verify_any,
+            verify::verify_size,
+            encoding::format_hash,
+            history::get_history,
+            history::clear_history,
+            quick::quick_fingerprint,
+            manifest::manifests_equivalent_by_content,
+            manifest::verify_manifest,
+            manifest::verify_bsd_manifest,
+            remote::verify_against_url,
+            manifest::generate_manifest,
+            directory::hash_directory,
+            directory::directory_root_hash,
+            directory::diff_directories,
+            store::store_content_addressed,
+            self_test::run_self_test,
+            blake2_var::calculate_blake2,
+            directory::estimate_hash_job,
+            compare::compare_files,
+            compare::find_duplicates,
+            export::export_results_json,
+            export::export_results_csv,
+            export::export_results_cbor,
+            export::import_results,
+            encoding::calculate_checksum_base64,
+            encoding::calculate_checksum_base32,
+            keyed::calculate_hmac,
+            keyed::calculate_siphash,
+            settings::set_start_hidden,
+            settings::set_window_offset,
+            settings::set_tray_rehash_on_click,
+            settings::set_audit_log,
+            clipboard::hash_clipboard_text,
+            clipboard::copy_hash_to_clipboard,
+            hash_type::detect_hash_type,
+            watch::watch_file,
+            watch::unwatch_file
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                app.state::<Operations>().cancel_all();
+                let _ = app.global_shortcut().unregister_all();
+            }
+        });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use md5::Md5;
+    use sha1::Sha1;
+    use sha2::{Digest, Sha256, Sha512};
     use std::fs::File;
     use std::io::Write;
     use tempfile::TempDir;
 
+    fn test_app() -> tauri::App<tauri::test::MockRuntime> {
+        let app = tauri::test::mock_app();
+        app.manage(Operations::default());
+        app.manage(LastHash::default());
+        app.manage(History::default());
+        app.manage(HashCache::default());
+        app
+    }
+
     fn create_test_file(content: &[u8]) -> (TempDir, String) {
         let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("test_file.txt");
+        // Canonical so results compare equal where the temp dir is a symlink
+        let file_path = temp_dir
+            .path()
+            .canonicalize()
+            .unwrap()
+            .join("test_file.txt");
         let mut file = File::create(&file_path).unwrap();
         file.write_all(content).unwrap();
         file.sync_all().unwrap();
@@ -182,7 +1142,7 @@ mod tests {
     #[test]
     fn test_calculate_empty_file() {
         let (_temp_dir, file_path) = create_test_file(b"");
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         assert_eq!(result.md5, "d41d8cd98f00b204e9800998ecf8427e");
         assert_eq!(result.sha1, "da39a3ee5e6b4b0d3255bfef95601890afd80709");
@@ -191,6 +1151,21 @@ mod tests {
             "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
         );
         assert_eq!(result.sha512, "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e");
+        assert_eq!(
+            result.blake3,
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+        assert_eq!(
+            result.sha3_256,
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(result.sha3_512, "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26");
+        assert_eq!(
+            result.sha224,
+            "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+        );
+        assert_eq!(result.sha384, "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b");
+        assert_eq!(result.adler32, "00000001");
         assert_eq!(result.file_size, 0);
     }
 
@@ -198,7 +1173,7 @@ mod tests {
     fn test_calculate_known_content() {
         let content = b"The quick brown fox jumps over the lazy dog";
         let (_temp_dir, file_path) = create_test_file(content);
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
         assert_eq!(result.sha1, "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12");
@@ -210,20 +1185,215 @@ mod tests {
         assert_eq!(result.file_size, 43);
     }
 
+    #[test]
+    fn test_calculate_known_content_blake3() {
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let (_temp_dir, file_path) = create_test_file(content);
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert_eq!(
+            result.blake3,
+            "2f1514181aadccd913abd94cfa592701a5686ab23f8df1dff1b74710febc6d4a"
+        );
+    }
+
+    #[test]
+    fn test_calculate_reports_canonical_path() {
+        let (temp_dir, file_path) = create_test_file(b"Test content");
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir(root.join("subdir")).unwrap();
+        let spelled = root
+            .join(".")
+            .join("subdir")
+            .join("..")
+            .join("test_file.txt");
+
+        let result = calculate_file_hash(&spelled.to_string_lossy(), &[Algorithm::Md5]).unwrap();
+        assert_eq!(result.path, file_path);
+        assert!(Path::new(&result.path).is_absolute());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_calculate_broken_symlink_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("dangling");
+        std::os::unix::fs::symlink(temp_dir.path().join("gone.txt"), &link).unwrap();
+
+        let error = calculate_file_hash(&link.to_string_lossy(), &[Algorithm::Md5]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().starts_with("cannot resolve "));
+    }
+
     #[test]
     fn test_calculate_file_metadata() {
         let content = b"Test content";
         let (_temp_dir, file_path) = create_test_file(content);
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         assert_eq!(result.file_size, 12);
-        assert!(result.modified.parse::<u64>().unwrap() > 0);
-        assert!(result.created.parse::<u64>().unwrap() > 0);
+        assert_eq!(result.path, file_path);
+        assert!(result.modified.unwrap() > 0);
+        assert!(result.created.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_file_name_and_parent_dir() {
+        let (temp_dir, file_path) = create_test_file(b"nested");
+        let result = calculate_file_hash(&file_path, &[Algorithm::Md5]).unwrap();
+        assert_eq!(result.file_name, "test_file.txt");
+        assert_eq!(
+            Path::new(&result.parent_dir),
+            temp_dir.path().canonicalize().unwrap()
+        );
+
+        let mut result = HashResult::default();
+        result.set_path("a/b/c.iso");
+        assert_eq!(
+            (result.file_name.as_str(), result.parent_dir.as_str()),
+            ("c.iso", "a/b")
+        );
+        result.set_path("c.iso");
+        assert_eq!(
+            (result.file_name.as_str(), result.parent_dir.as_str()),
+            ("c.iso", "")
+        );
+        result.set_path("/");
+        assert_eq!(
+            (result.file_name.as_str(), result.parent_dir.as_str()),
+            ("/", "")
+        );
+    }
+
+    #[test]
+    fn test_timestamps_suspect() {
+        let with_times = |created, modified| HashResult {
+            created: Some(created),
+            modified: Some(modified),
+            ..Default::default()
+        };
+        let copied = with_times(1_700_000_100, 1_700_000_000);
+        assert!(timestamps_suspect(copied.created, copied.modified));
+
+        let normal = with_times(1_700_000_000, 1_700_000_100);
+        assert!(!timestamps_suspect(normal.created, normal.modified));
+        // Rounding between the two fields isn't skew
+        let rounded = with_times(1_700_000_001, 1_700_000_000);
+        assert!(!timestamps_suspect(rounded.created, rounded.modified));
+        assert!(!timestamps_suspect(None, Some(0)));
+
+        let (_temp_dir, file_path) = create_test_file(b"fresh");
+        assert!(
+            !calculate_file_hash(&file_path, &[Algorithm::Md5])
+                .unwrap()
+                .timestamps_suspect
+        );
+    }
+
+    #[test]
+    fn test_unavailable_timestamp_is_none() {
+        let unsupported = io::Error::new(io::ErrorKind::Unsupported, "creation time");
+        assert_eq!(timestamp_fields(Err(unsupported)), (None, String::new()));
+
+        let (seconds, iso) = timestamp_fields(Ok(UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+        assert_eq!(seconds, Some(1_700_000_000));
+        assert_eq!(iso, "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn test_iso_timestamps_match_epoch() {
+        let (_temp_dir, file_path) = create_test_file(b"Timestamp test");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        let modified = DateTime::parse_from_rfc3339(&result.modified_iso).unwrap();
+        let created = DateTime::parse_from_rfc3339(&result.created_iso).unwrap();
+        assert_eq!(modified.timestamp() as u64, result.modified.unwrap());
+        assert_eq!(created.timestamp() as u64, result.created.unwrap());
+        assert!(result.modified_iso.ends_with('Z'));
+    }
+
+    #[test]
+    fn test_iso8601_format() {
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(1_705_322_096);
+        assert_eq!(iso8601_utc(time), "2024-01-15T12:34:56Z");
+    }
+
+    #[test]
+    fn test_readonly_file() {
+        let (_temp_dir, file_path) = create_test_file(b"locked");
+        let writable = calculate_file_hash(&file_path, &[Algorithm::Md5]).unwrap();
+        assert!(!writable.readonly);
+
+        let mut permissions = std::fs::metadata(&file_path).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&file_path, permissions).unwrap();
+
+        let result = calculate_file_hash(&file_path, &[Algorithm::Md5]).unwrap();
+        assert!(result.readonly);
+        #[cfg(unix)]
+        assert_eq!(result.mode & 0o222, 0);
+    }
+
+    #[test]
+    fn test_mime_type_from_magic_bytes() {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend_from_slice(&[0u8; 64]);
+        let (_png_dir, png_path) = create_test_file(&png);
+        let mut zip = b"PK\x03\x04".to_vec();
+        zip.extend_from_slice(&[0u8; 64]);
+        let (_zip_dir, zip_path) = create_test_file(&zip);
+
+        let png_result = calculate_file_hash(&png_path, &[Algorithm::Md5]).unwrap();
+        let zip_result = calculate_file_hash(&zip_path, &[Algorithm::Md5]).unwrap();
+
+        assert_eq!(png_result.mime_type, "image/png");
+        assert_eq!(zip_result.mime_type, "application/zip");
+    }
+
+    #[test]
+    fn test_mime_type_unknown() {
+        let (_temp_dir, file_path) = create_test_file(b"plain words");
+        let (_empty_dir, empty_path) = create_test_file(b"");
+
+        let result = calculate_file_hash(&file_path, &[Algorithm::Md5]).unwrap();
+        let empty = calculate_file_hash(&empty_path, &[Algorithm::Md5]).unwrap();
+
+        assert_eq!(result.mime_type, "application/octet-stream");
+        assert_eq!(empty.mime_type, "application/octet-stream");
+    }
+
+    #[test]
+    fn test_calculate_directory_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let error =
+            calculate_file_hash(&temp_dir.path().to_string_lossy(), &Algorithm::ALL).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::IsADirectory);
+        assert_eq!(error.to_string(), "path is a directory");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_calculate_special_files_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let fifo = temp_dir.path().join("pipe");
+        let status = std::process::Command::new("mkfifo")
+            .arg(&fifo)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        // Neither call may block: the FIFO has no writer and /dev/zero never ends
+        for path in [fifo.to_string_lossy().to_string(), "/dev/zero".to_string()] {
+            let error = calculate_file_hash(&path, &[Algorithm::Md5]).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            assert_eq!(error.to_string(), "not a regular file");
+        }
     }
 
     #[test]
     fn test_calculate_nonexistent_file() {
-        let result = calculate_file_hash("/nonexistent/file/path.txt");
+        let result = calculate_file_hash("/nonexistent/file/path.txt", &Algorithm::ALL);
         assert!(result.is_err());
     }
 
@@ -231,7 +1401,7 @@ mod tests {
     fn test_hash_format() {
         let content = b"Format test";
         let (_temp_dir, file_path) = create_test_file(content);
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         // Verify hash formats (lowercase hex)
         assert_eq!(result.md5.len(), 32);
@@ -250,6 +1420,123 @@ mod tests {
 
         assert_eq!(result.sha512.len(), 128);
         assert!(result.sha512.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert_eq!(result.blake3.len(), 64);
+        assert!(result.blake3.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert_eq!(result.sha3_256.len(), 64);
+        assert_eq!(result.sha3_512.len(), 128);
+        assert_eq!(result.sha224.len(), 56);
+        assert_eq!(result.sha384.len(), 96);
+    }
+
+    #[test]
+    fn test_fingerprint_groups_sha256_prefix() {
+        let (_temp_dir, file_path) =
+            create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert_eq!(result.fingerprint, "d7a8 fbb3 07d7 8094");
+        assert_eq!(result.fingerprint.replace(' ', ""), result.sha256[..16]);
+    }
+
+    #[test]
+    fn test_fingerprint_empty_without_sha256() {
+        let (_temp_dir, file_path) = create_test_file(b"data");
+        let result = calculate_file_hash(&file_path, &[Algorithm::Md5]).unwrap();
+
+        assert!(result.fingerprint.is_empty());
+    }
+
+    #[test]
+    fn test_partial_results_when_one_algorithm_fails() {
+        let mut result = HashResult::default();
+        apply_digests(
+            &mut result,
+            &[Algorithm::Md5, Algorithm::Sha256],
+            vec![
+                Ok(vec![0xab, 0xcd]),
+                Err("hasher thread panicked".to_string()),
+            ],
+        );
+
+        assert_eq!(result.md5, "abcd");
+        assert!(result.sha256.is_empty());
+        assert_eq!(
+            result.errors.get("sha256").map(String::as_str),
+            Some("hasher thread panicked")
+        );
+    }
+
+    #[test]
+    fn test_no_errors_on_happy_path() {
+        let (_temp_dir, file_path) = create_test_file(b"all good");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert!(result.errors.is_empty());
+        assert!(Algorithm::ALL
+            .into_iter()
+            .all(|algorithm| !result.clone().digest_mut(algorithm).is_empty()));
+    }
+
+    #[test]
+    fn test_hash_piped_input() {
+        let input = io::Cursor::new(b"The quick brown fox jumps over the lazy dog".to_vec());
+        let result = hash_piped(input, &Algorithm::ALL, None, |_| {}, None).unwrap();
+
+        assert_eq!(result.path, "-");
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(result.file_size, 43);
+        assert!(result.modified.is_none());
+        assert!(result.created_iso.is_empty());
+    }
+
+    #[test]
+    fn test_file_size_counts_bytes_read() {
+        // A reader shorter than the size claimed up front, as when a file
+        // is truncated mid-hash
+        let result =
+            hash_content(&b"short"[..], 64, &[Algorithm::Md5], None, |_| {}, None).unwrap();
+
+        assert_eq!(result.file_size, 5);
+        assert_eq!(result.metadata_size, 64);
+    }
+
+    #[test]
+    fn test_file_size_matches_metadata_when_stable() {
+        let (_temp_dir, file_path) = create_test_file(b"steady");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert_eq!(result.file_size, 6);
+        assert_eq!(result.metadata_size, 6);
+    }
+
+    #[test]
+    fn test_throughput_reported() {
+        let (_temp_dir, file_path) = create_test_file(&vec![0x5A; 1024 * 1024]);
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        // Mostly checks the fields exist and stay sane on a fast machine
+        assert!(result.duration_ms < 60_000);
+        assert!(result.throughput_mbps.is_finite());
+        assert!(result.throughput_mbps >= 0.0);
+    }
+
+    #[test]
+    fn test_throughput_zero_duration() {
+        assert_eq!(throughput_mbps(1024, Duration::ZERO), 0.0);
+        assert_eq!(throughput_mbps(2_000_000, Duration::from_secs(2)), 1.0);
+    }
+
+    #[test]
+    fn test_xxh3_deterministic() {
+        let (_temp_dir, file_path) = create_test_file(b"Fingerprint me");
+        let first = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+        let second = calculate_file_hash(&file_path, &[Algorithm::Xxh3]).unwrap();
+
+        assert_eq!(first.xxh3.len(), 16);
+        assert!(first.xxh3.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(first.xxh3, second.xxh3);
     }
 
     #[test]
@@ -258,8 +1545,8 @@ mod tests {
         let (_temp_dir1, file_path1) = create_test_file(content);
         let (_temp_dir2, file_path2) = create_test_file(content);
 
-        let result1 = calculate_file_hash(&file_path1).unwrap();
-        let result2 = calculate_file_hash(&file_path2).unwrap();
+        let result1 = calculate_file_hash(&file_path1, &Algorithm::ALL).unwrap();
+        let result2 = calculate_file_hash(&file_path2, &Algorithm::ALL).unwrap();
 
         assert_eq!(result1.md5, result2.md5);
         assert_eq!(result1.sha1, result2.sha1);
@@ -272,8 +1559,8 @@ mod tests {
         let (_temp_dir1, file_path1) = create_test_file(b"Content A");
         let (_temp_dir2, file_path2) = create_test_file(b"Content B");
 
-        let result1 = calculate_file_hash(&file_path1).unwrap();
-        let result2 = calculate_file_hash(&file_path2).unwrap();
+        let result1 = calculate_file_hash(&file_path1, &Algorithm::ALL).unwrap();
+        let result2 = calculate_file_hash(&file_path2, &Algorithm::ALL).unwrap();
 
         assert_ne!(result1.md5, result2.md5);
         assert_ne!(result1.sha1, result2.sha1);
@@ -285,7 +1572,7 @@ mod tests {
     fn test_binary_content() {
         let content: Vec<u8> = (0..=255).collect();
         let (_temp_dir, file_path) = create_test_file(&content);
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         assert_eq!(result.file_size, 256);
         assert_eq!(result.md5.len(), 32);
@@ -296,37 +1583,787 @@ mod tests {
     fn test_large_file() {
         let content = vec![0xAB; 1024 * 1024]; // 1MB
         let (_temp_dir, file_path) = create_test_file(&content);
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         assert_eq!(result.file_size, 1024 * 1024);
         assert!(result.md5.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn test_streamed_matches_full_buffer() {
+        // Spans several chunks and ends mid-chunk
+        let content: Vec<u8> = (0..algorithms::CHUNK_SIZE * 3 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let (_temp_dir, file_path) = create_test_file(&content);
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert_eq!(result.md5, format!("{:x}", Md5::digest(&content)));
+        assert_eq!(result.sha1, format!("{:x}", Sha1::digest(&content)));
+        assert_eq!(result.sha256, format!("{:x}", Sha256::digest(&content)));
+        assert_eq!(result.sha512, format!("{:x}", Sha512::digest(&content)));
+        assert_eq!(result.file_size, content.len() as u64);
+    }
+
+    #[test]
+    fn test_parallel_digests_keep_algorithm_order() {
+        let (_temp_dir, file_path) =
+            create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let result = calculate_file_hash(&file_path, &[Algorithm::Sha512, Algorithm::Md5]).unwrap();
+
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(result.sha512, "07e547d9586f6a73f73fbac0435ed76951218fb7d0c8d788a309d785436bbb642e93a252a954f23912547d1e8a3b5ed6e1bfd7097821233fa0538f3db854fee6");
+        assert!(result.sha256.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_buffer_size_does_not_change_digests() {
+        let content: Vec<u8> = (0..300_000u32).map(|i| (i % 241) as u8).collect();
+        let (_temp_dir, file_path) = create_test_file(&content);
+
+        let small = calculate_checksum_with_buffer(file_path.clone(), Some(4096))
+            .await
+            .unwrap();
+        let large = calculate_checksum_with_buffer(file_path, Some(1024 * 1024))
+            .await
+            .unwrap();
+
+        assert_eq!(small.sha256, large.sha256);
+        assert_eq!(small.md5, large.md5);
+        assert_eq!(small.xxh3, large.xxh3);
+        assert_eq!(small.file_size, large.file_size);
+    }
+
+    #[test]
+    fn test_progress_reports_final_size() {
+        let content = vec![0xCD; 10 * 1024 * 1024 + 5];
+        let (_temp_dir, file_path) = create_test_file(&content);
+        let mut events = Vec::new();
+        let result = calculate_file_hash_with_progress(
+            &file_path,
+            &[Algorithm::Md5],
+            None,
+            |p| events.push(p),
+            None,
+        )
+        .unwrap();
+
+        // Two 4 MiB reports plus the final one
+        assert_eq!(events.len(), 3);
+        assert!(events.windows(2).all(|w| w[0].bytes_done < w[1].bytes_done));
+        assert!(events.iter().all(|p| p.total == content.len() as u64));
+        assert_eq!(events.last().unwrap().bytes_done, result.file_size);
+    }
+
+    #[test]
+    fn test_progress_empty_file() {
+        let (_temp_dir, file_path) = create_test_file(b"");
+        let mut events = Vec::new();
+        calculate_file_hash_with_progress(
+            &file_path,
+            &Algorithm::ALL,
+            None,
+            |p| events.push(p),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            events,
+            vec![ProgressPayload {
+                bytes_done: 0,
+                total: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cancel_mid_stream() {
+        let content = vec![0xEF; 10 * 1024 * 1024];
+        let (_temp_dir, file_path) = create_test_file(&content);
+        let cancel = AtomicBool::new(false);
+        let mut reports = 0;
+
+        // Trip the flag at the first progress report, well before EOF
+        let result = calculate_file_hash_with_progress(
+            &file_path,
+            &Algorithm::ALL,
+            None,
+            |_| {
+                reports += 1;
+                cancel.store(true, std::sync::atomic::Ordering::SeqCst);
+            },
+            Some(&cancel),
+        );
+
+        assert_eq!(result.unwrap_err().to_string(), "cancelled");
+        assert_eq!(reports, 1);
+    }
+
     #[test]
     fn test_unicode_content() {
         let content = "Hello, 世界! 🌍".as_bytes();
         let (_temp_dir, file_path) = create_test_file(content);
-        let result = calculate_file_hash(&file_path).unwrap();
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
 
         assert!(result.file_size > 0);
         assert_eq!(result.md5.len(), 32);
         assert_eq!(result.sha256.len(), 64);
     }
 
+    #[test]
+    fn test_placeholder_zero_byte_file() {
+        let (_temp_dir, file_path) = create_test_file(b"");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert!(result.is_empty_or_placeholder);
+    }
+
+    #[test]
+    fn test_is_empty_only_for_zero_bytes() {
+        let (_temp_dir, file_path) = create_test_file(b"");
+        assert!(
+            calculate_file_hash(&file_path, &[Algorithm::Md5])
+                .unwrap()
+                .is_empty
+        );
+
+        // Whitespace counts as a placeholder but not as empty
+        let (_temp_dir, file_path) = create_test_file(b"\n");
+        let result = calculate_file_hash(&file_path, &[Algorithm::Md5]).unwrap();
+        assert!(!result.is_empty);
+        assert!(result.is_empty_or_placeholder);
+    }
+
+    #[test]
+    fn test_placeholder_whitespace_only_file() {
+        let (_temp_dir, file_path) = create_test_file(b"\n");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+        assert!(result.is_empty_or_placeholder);
+
+        let (_temp_dir, file_path) = create_test_file(b"  \t\r\n  ");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+        assert!(result.is_empty_or_placeholder);
+    }
+
+    #[test]
+    fn test_placeholder_normal_file() {
+        let (_temp_dir, file_path) = create_test_file(b"  real content\n");
+        let result = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        assert!(!result.is_empty_or_placeholder);
+    }
+
+    #[tokio::test]
+    async fn test_detect_placeholder_command() {
+        let (_temp_dir, empty_path) = create_test_file(b" \n");
+        let (_temp_dir2, normal_path) = create_test_file(b"data");
+
+        assert!(detect_placeholder(empty_path).await.unwrap());
+        assert!(!detect_placeholder(normal_path).await.unwrap());
+        assert!(detect_placeholder("/nonexistent/file.txt".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_detect_placeholder_rejects_non_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_string_lossy().to_string();
+
+        assert_eq!(
+            detect_placeholder(dir).await.unwrap_err(),
+            "path is a directory"
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            detect_placeholder("/dev/zero".to_string())
+                .await
+                .unwrap_err(),
+            "not a regular file"
+        );
+    }
+
     #[tokio::test]
     async fn test_calculate_checksum_command() {
+        let app = test_app();
         let content = b"Command test";
         let (_temp_dir, file_path) = create_test_file(content);
-        let result = calculate_checksum(file_path).await;
+        let result = calculate_checksum(
+            HashRequest::new(file_path),
+            app.state(),
+            app.handle().clone(),
+        )
+        .await;
 
         assert!(result.is_ok());
         let hash_result = result.unwrap();
         assert_eq!(hash_result.file_size, 12);
+
+        let json = serde_json::to_value(&hash_result).unwrap();
+        assert_eq!(json["blake3"], hash_result.blake3);
+        assert_eq!(json["sha3_256"], hash_result.sha3_256);
+        assert_eq!(json["sha3_512"], hash_result.sha3_512);
+        assert_eq!(json["sha224"], hash_result.sha224);
+        assert_eq!(json["sha384"], hash_result.sha384);
+    }
+
+    #[tokio::test]
+    async fn test_hash_request_minimal() {
+        let app = test_app();
+        let (_temp_dir, file_path) =
+            create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let request: HashRequest =
+            serde_json::from_value(serde_json::json!({ "path": file_path })).unwrap();
+        assert_eq!(request, HashRequest::new(file_path.clone()));
+
+        let result = calculate_checksum(request, app.state(), app.handle().clone())
+            .await
+            .unwrap();
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(result.file_size, 43);
+
+        let shim = calculate_checksum_path(file_path, app.state(), app.handle().clone())
+            .await
+            .unwrap();
+        assert_eq!(shim, result);
+    }
+
+    #[tokio::test]
+    async fn test_hash_request_fully_specified() {
+        let app = test_app();
+        let (_temp_dir, file_path) =
+            create_test_file(b"xxThe quick brown fox jumps over the lazy dogxx");
+        let request: HashRequest = serde_json::from_value(serde_json::json!({
+            "path": file_path,
+            "algorithms": ["md5", "sha256"],
+            "op_id": "op-request",
+            "uppercase": true,
+            "follow_symlinks": true,
+            "bypass_cache": true,
+            "skip_metadata": false,
+            "range": [2, 43],
+            "buffer_size": 4096,
+        }))
+        .unwrap();
+        assert_eq!(request.range, Some((2, 43)));
+        assert_eq!(request.buffer_size, Some(4096));
+
+        let result = calculate_checksum(request, app.state(), app.handle().clone())
+            .await
+            .unwrap();
+        assert_eq!(result.md5, "9E107D9D372BB6826BD81D3542A419D6");
+        assert_eq!(
+            result.sha256,
+            "D7A8FBB307D7809469CA9ABCB0082E4F8D5651E46D3CDB762D02D0BF37C9E592"
+        );
+        assert!(result.sha1.is_empty());
+        assert_eq!(result.file_size, 43);
+        assert_eq!(result.metadata_size, 43);
+        // Partial results are never cached
+        assert_eq!(app.state::<HashCache>().hits(), 0);
+
+        let stdin_range = HashRequest {
+            path: STDIN_PATH.to_string(),
+            range: Some((0, 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            calculate_checksum(stdin_range, app.state(), app.handle().clone()).await,
+            Err(ChecksumError::InvalidArgument(_))
+        ));
+    }
+
+    async fn checksum_cached(
+        app: &tauri::App<tauri::test::MockRuntime>,
+        path: &str,
+        bypass_cache: Option<bool>,
+    ) -> HashResult {
+        calculate_checksum(
+            HashRequest {
+                path: path.to_string(),
+                bypass_cache,
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_uses_cache_until_file_changes() {
+        let app = test_app();
+        let (_temp_dir, file_path) = create_test_file(b"Cache test");
+        let hits = || app.state::<HashCache>().hits();
+
+        let first = checksum_cached(&app, &file_path, None).await;
+        assert_eq!(hits(), 0);
+        assert_eq!(checksum_cached(&app, &file_path, None).await, first);
+        assert_eq!(hits(), 1);
+
+        checksum_cached(&app, &file_path, Some(true)).await;
+        assert_eq!(hits(), 1);
+
+        // Same size, new mtime: the stale digest must not be served
+        std::fs::write(&file_path, b"Cache TEST").unwrap();
+        let file = File::options().write(true).open(&file_path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        let changed = checksum_cached(&app, &file_path, None).await;
+        assert_eq!(hits(), 1);
+        assert_ne!(changed.sha256, first.sha256);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_skip_metadata() {
+        let app = test_app();
+        let (_temp_dir, file_path) =
+            create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let result = calculate_checksum(
+            HashRequest {
+                path: file_path.clone(),
+                skip_metadata: Some(true),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(
+            result.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(result.file_size, 43);
+        assert_eq!(result.path, file_path);
+        assert_eq!(result.metadata_size, 0);
+        assert_eq!(result.modified, None);
+        assert_eq!(result.created, None);
+        assert!(result.modified_iso.is_empty());
+        assert!(result.created_iso.is_empty());
+        assert_eq!(result.mode, 0);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_adds_to_history() {
+        let app = test_app();
+        let (_temp_dir, file_path) = create_test_file(b"History test");
+        let result = calculate_checksum(
+            HashRequest::new(file_path),
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(app.state::<History>().entries(), vec![result]);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_selected_algorithms() {
+        let app = test_app();
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let (_temp_dir, file_path) = create_test_file(content);
+        let result = calculate_checksum(
+            HashRequest {
+                path: file_path,
+                algorithms: Some(vec!["sha256".to_string()]),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert!(result.md5.is_empty());
+        assert!(result.sha1.is_empty());
+        assert!(result.sha512.is_empty());
+        assert!(result.blake3.is_empty());
+        assert!(result.sha3_256.is_empty());
+        assert!(result.sha3_512.is_empty());
+        assert_eq!(result.file_size, 43);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_empty_selection_computes_all() {
+        let app = test_app();
+        let (_temp_dir, file_path) = create_test_file(b"Command test");
+        let result = calculate_checksum(
+            HashRequest {
+                path: file_path,
+                algorithms: Some(Vec::new()),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.md5.len(), 32);
+        assert_eq!(result.sha512.len(), 128);
+        assert_eq!(result.sha3_512.len(), 128);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_unknown_algorithm() {
+        let app = test_app();
+        let (_temp_dir, file_path) = create_test_file(b"Command test");
+        let result = calculate_checksum(
+            HashRequest {
+                path: file_path,
+                algorithms: Some(vec!["crc32".to_string()]),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Err(ChecksumError::InvalidArgument(
+                "unsupported algorithm: crc32".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_uppercase() {
+        let app = test_app();
+        let (_temp_dir, file_path) = create_test_file(b"Uppercase test");
+        let lower = calculate_checksum(
+            HashRequest::new(file_path.clone()),
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+        let upper = calculate_checksum(
+            HashRequest {
+                path: file_path,
+                uppercase: Some(true),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+
+        for (l, u) in [
+            (&lower.md5, &upper.md5),
+            (&lower.sha1, &upper.sha1),
+            (&lower.sha224, &upper.sha224),
+            (&lower.sha256, &upper.sha256),
+            (&lower.sha384, &upper.sha384),
+            (&lower.sha512, &upper.sha512),
+            (&lower.blake3, &upper.blake3),
+            (&lower.sha3_256, &upper.sha3_256),
+            (&lower.sha3_512, &upper.sha3_512),
+            (&lower.xxh3, &upper.xxh3),
+            (&lower.adler32, &upper.adler32),
+        ] {
+            assert_eq!(l.len(), u.len());
+            assert_eq!(&u.to_lowercase(), l);
+            assert!(!u.chars().any(|c| c.is_ascii_lowercase()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_unregisters_operation() {
+        let app = test_app();
+        let (_temp_dir, file_path) = create_test_file(b"Command test");
+        calculate_checksum(
+            HashRequest {
+                path: file_path,
+                op_id: Some("op-1".to_string()),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+
+        // The operation is gone once the hash finishes
+        assert!(!operations::cancel_checksum(
+            "op-1".to_string(),
+            app.state()
+        ));
     }
 
     #[tokio::test]
     async fn test_calculate_checksum_command_error() {
-        let result = calculate_checksum("/nonexistent/file.txt".to_string()).await;
-        assert!(result.is_err());
+        let app = test_app();
+        let result = calculate_checksum(
+            HashRequest::new("/nonexistent/file.txt".to_string()),
+            app.state(),
+            app.handle().clone(),
+        )
+        .await;
+        assert_eq!(result, Err(ChecksumError::NotFound));
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_mangled_path() {
+        let app = test_app();
+        let result = calculate_checksum(
+            HashRequest::new("/tmp/caf\u{fffd}.txt".to_string()),
+            app.state(),
+            app.handle().clone(),
+        )
+        .await;
+        assert_eq!(
+            result,
+            Err(ChecksumError::InvalidArgument(
+                "path is not valid UTF-8".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_directory_error() {
+        let app = test_app();
+        let temp_dir = TempDir::new().unwrap();
+        let result = calculate_checksum(
+            HashRequest::new(temp_dir.path().to_string_lossy().to_string()),
+            app.state(),
+            app.handle().clone(),
+        )
+        .await;
+        assert_eq!(result, Err(ChecksumError::IsDirectory));
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_text_matches_file() {
+        let content = "The quick brown fox jumps over the lazy dog";
+        let (_temp_dir, file_path) = create_test_file(content.as_bytes());
+        let from_file = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        let result = calculate_checksum_text(content.to_string(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.sha256, from_file.sha256);
+        assert_eq!(
+            result.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(result.file_size, 43);
+        assert!(result.path.is_empty());
+        assert!(result.modified.is_none());
+        assert!(result.created_iso.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hash_glob_matches_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        for name in ["b.txt", "a.txt", "c.iso", "notes.md"] {
+            std::fs::write(root.join(name), name).unwrap();
+        }
+        // A directory whose name matches is skipped
+        std::fs::create_dir(root.join("dir.txt")).unwrap();
+        let pattern = |glob: &str| root.join(glob).to_string_lossy().to_string();
+
+        let results = hash_glob(pattern("*.txt")).await.unwrap();
+        let names: Vec<&str> = results.iter().map(|r| r.file_name.as_str()).collect();
+        assert_eq!(names, vec!["a.txt", "b.txt"]);
+        assert_eq!(results[0].file_size, 5);
+
+        assert!(hash_glob(pattern("*.zip")).await.unwrap().is_empty());
+        assert!(hash_glob(pattern("[")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_text_encoding() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        let utf8 = calculate_checksum_text(text.to_string(), Some("utf-8".into()))
+            .await
+            .unwrap();
+        let utf16 = calculate_checksum_text(text.to_string(), Some("utf-16le".into()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            utf8.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_ne!(utf16.sha256, utf8.sha256);
+        assert_eq!(utf16.file_size, 86);
+        assert!(
+            calculate_checksum_text(text.to_string(), Some("koi8-r".into()))
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_hash_paths_keeps_order() {
+        let (_dir_a, path_a) = create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let (_dir_b, path_b) = create_test_file(b"");
+
+        let results = hash_paths(&[path_a.clone(), path_b.clone()]).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].path, path_a);
+        assert_eq!(
+            results[0].sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(results[1].path, path_b);
+        assert_eq!(results[1].file_size, 0);
+
+        assert!(hash_paths(&[]).unwrap().is_empty());
+        assert!(hash_paths(&[path_a, "/nonexistent/file".into()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_bytes_matches_file() {
+        let content: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+        let (_temp_dir, file_path) = create_test_file(&content);
+        let from_file = calculate_file_hash(&file_path, &Algorithm::ALL).unwrap();
+
+        let result = calculate_checksum_bytes(content).await.unwrap();
+
+        assert_eq!(result.md5, from_file.md5);
+        assert_eq!(result.sha256, from_file.sha256);
+        assert_eq!(result.blake3, from_file.blake3);
+        assert_eq!(result.file_size, 100_000);
+        assert!(result.path.is_empty());
+        assert!(result.modified.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_gzip_hashes_decompressed() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content).unwrap();
+        let (_temp_dir, file_path) = create_test_file(&encoder.finish().unwrap());
+
+        let result = calculate_checksum_gzip(file_path.clone()).await.unwrap();
+        assert_eq!(
+            result.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(result.file_size, 43);
+        assert_eq!(result.path, file_path);
+
+        let (_plain_dir, plain_path) = create_test_file(content);
+        assert!(calculate_checksum_gzip(plain_path).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_normalized_line_endings() {
+        let (_unix_dir, unix_path) = create_test_file(b"line one\nline two\n");
+        let (_dos_dir, dos_path) = create_test_file(b"\xEF\xBB\xBFline one\r\nline two\r\n");
+
+        let unix = calculate_checksum_normalized(unix_path.clone())
+            .await
+            .unwrap();
+        let dos = calculate_checksum_normalized(dos_path.clone())
+            .await
+            .unwrap();
+        assert_eq!(unix.sha256, dos.sha256);
+        assert_eq!(dos.file_size, 18);
+        assert_eq!(dos.metadata_size, 23);
+
+        let unix_raw = calculate_file_hash(&unix_path, &[Algorithm::Sha256]).unwrap();
+        let dos_raw = calculate_file_hash(&dos_path, &[Algorithm::Sha256]).unwrap();
+        assert_ne!(unix_raw.sha256, dos_raw.sha256);
+        assert_eq!(unix_raw.sha256, unix.sha256);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_range_mid_file() {
+        let content = b"HEADERThe quick brown fox jumps over the lazy dogTRAILER";
+        let (_temp_dir, file_path) = create_test_file(content);
+
+        let result = calculate_checksum_range(file_path.clone(), 6, 43)
+            .await
+            .unwrap();
+
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(
+            result.sha256,
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(result.file_size, 43);
+        assert_eq!(result.path, file_path);
+    }
+
+    #[tokio::test]
+    async fn test_calculate_checksum_range_out_of_bounds() {
+        let (_temp_dir, file_path) = create_test_file(b"0123456789");
+
+        assert!(calculate_checksum_range(file_path.clone(), 8, 3)
+            .await
+            .is_err());
+        assert!(calculate_checksum_range(file_path.clone(), u64::MAX, 2)
+            .await
+            .is_err());
+        // A range ending exactly at EOF is fine
+        let tail = calculate_checksum_range(file_path, 7, 3).await.unwrap();
+        assert_eq!(tail.file_size, 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_calculate_checksum_symlink_modes() {
+        let app = test_app();
+        let (temp_dir, file_path) =
+            create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let link = temp_dir.path().join("link.txt");
+        std::os::unix::fs::symlink(&file_path, &link).unwrap();
+        let link = link.to_string_lossy().to_string();
+
+        let followed = calculate_checksum(
+            HashRequest {
+                path: link.clone(),
+                follow_symlinks: Some(true),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(followed.md5, "9e107d9d372bb6826bd81d3542a419d6");
+
+        let refused = calculate_checksum(
+            HashRequest {
+                path: link,
+                follow_symlinks: Some(false),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await;
+        assert_eq!(refused, Err(ChecksumError::IsSymlink));
+
+        // Regular files are unaffected by the option
+        assert!(calculate_checksum(
+            HashRequest {
+                path: file_path,
+                follow_symlinks: Some(false),
+                ..Default::default()
+            },
+            app.state(),
+            app.handle().clone(),
+        )
+        .await
+        .is_ok());
     }
 }
@@ -0,0 +1,527 @@
+use crate::algorithms::{self, Algorithm};
+use crate::verify;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+/// One `<hash>  <filename>` line of a sha256sum-style manifest.
+#[derive(Debug, PartialEq)]
+pub struct ManifestEntry {
+    /// Expected digest, lowercased.
+    pub hash: String,
+    pub filename: String,
+}
+
+/// Parses a single manifest line in GNU coreutils format, accepting both
+/// the text (`hash  name`) and binary (`hash *name`) conventions.
+pub(crate) fn parse_line(line: &str) -> Option<ManifestEntry> {
+    let (hash, rest) = line.split_once(' ')?;
+    if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let filename = rest.strip_prefix(' ').or_else(|| rest.strip_prefix('*'))?;
+    if filename.is_empty() {
+        return None;
+    }
+    Some(ManifestEntry {
+        hash: hash.to_ascii_lowercase(),
+        filename: filename.to_string(),
+    })
+}
+
+/// Parses manifest text, skipping blank lines and `#` comments.
+pub fn parse_manifest(content: &str) -> Result<Vec<ManifestEntry>, String> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            parse_line(line).ok_or_else(|| format!("line {}: malformed manifest entry", i + 1))
+        })
+        .collect()
+}
+
+fn read_manifest(path: &str) -> Result<Vec<ManifestEntry>, String> {
+    parse_manifest(&read_manifest_text(path)?)
+}
+
+fn read_manifest_text(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Maps a BSD tag such as `SHA256` or `SHA3-256` to an [`Algorithm`].
+fn bsd_algorithm(tag: &str) -> Option<Algorithm> {
+    let tag: String = tag
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    // `rmd160` is the name BSD tools print for RIPEMD-160
+    if tag == "rmd160" {
+        return Some(Algorithm::Ripemd160);
+    }
+    Algorithm::ALL
+        .into_iter()
+        .find(|algorithm| algorithm.id().replace('_', "") == tag)
+}
+
+/// Parses one BSD-style `MD5 (filename) = hash` line.
+fn parse_bsd_line(line: &str) -> Option<(Algorithm, ManifestEntry)> {
+    let (tag, rest) = line.split_once(" (")?;
+    let (filename, hash) = rest.rsplit_once(") = ")?;
+    let hash = hash.trim_end();
+    if filename.is_empty() || hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((
+        bsd_algorithm(tag)?,
+        ManifestEntry {
+            hash: hash.to_ascii_lowercase(),
+            filename: filename.to_string(),
+        },
+    ))
+}
+
+/// Parses BSD tagged manifest text, where each line names its algorithm.
+/// Blank lines and `#` comments are skipped as in [`parse_manifest`].
+pub fn parse_bsd_manifest(content: &str) -> Result<Vec<(Algorithm, ManifestEntry)>, String> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            parse_bsd_line(line).ok_or_else(|| format!("line {}: malformed BSD entry", i + 1))
+        })
+        .collect()
+}
+
+fn hash_counts(entries: &[ManifestEntry]) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for entry in entries {
+        *counts.entry(entry.hash.as_str()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Returns true when both manifests contain the same content hashes the
+/// same number of times. Filenames and directory layout are ignored, which
+/// suits mirrors that reorganise files but must carry identical contents.
+#[tauri::command]
+pub async fn manifests_equivalent_by_content(
+    manifest_a: String,
+    manifest_b: String,
+) -> Result<bool, String> {
+    let entries_a = read_manifest(&manifest_a)?;
+    let entries_b = read_manifest(&manifest_b)?;
+    Ok(hash_counts(&entries_a) == hash_counts(&entries_b))
+}
+
+/// Outcome of checking one manifest line against the file on disk.
+#[derive(serde::Serialize, Debug)]
+pub struct VerifyEntry {
+    pub filename: String,
+    /// Id of the algorithm the entry was checked with.
+    pub algorithm: String,
+    pub expected: String,
+    /// Empty when the file couldn't be read.
+    pub actual: String,
+    pub ok: bool,
+}
+
+/// Resolves a manifest filename under `base_dir`, or `None` when it is
+/// absolute or climbs out with `..`, so a crafted manifest can't make us
+/// read and hash files elsewhere on disk.
+fn contained_path(base_dir: &Path, filename: &str) -> Option<PathBuf> {
+    let relative = Path::new(filename);
+    relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        .then(|| base_dir.join(relative))
+}
+
+fn verify_entry(entry: ManifestEntry, base_dir: &Path, algorithm: Algorithm) -> VerifyEntry {
+    match contained_path(base_dir, &entry.filename) {
+        Some(path) => verify_path(&path, entry, algorithm),
+        None => VerifyEntry {
+            filename: entry.filename,
+            algorithm: algorithm.id().to_string(),
+            expected: entry.hash,
+            actual: String::new(),
+            ok: false,
+        },
+    }
+}
+
+/// Checks the file at `path` against `entry.hash`, reporting it under
+/// `entry.filename`.
+pub(crate) fn verify_path(path: &Path, entry: ManifestEntry, algorithm: Algorithm) -> VerifyEntry {
+    let actual = File::open(path)
+        .and_then(|file| algorithms::hash_reader(file, algorithm))
+        .unwrap_or_default();
+    VerifyEntry {
+        ok: !actual.is_empty() && verify::constant_time_eq(&actual, &entry.hash),
+        filename: entry.filename,
+        algorithm: algorithm.id().to_string(),
+        expected: entry.hash,
+        actual,
+    }
+}
+
+/// Checks every entry of a sha256sum-style manifest, resolving filenames
+/// relative to `base_dir`. Missing or unreadable files are reported as
+/// failed entries rather than aborting the whole check.
+#[tauri::command]
+pub async fn verify_manifest(
+    manifest_path: String,
+    base_dir: String,
+) -> Result<Vec<VerifyEntry>, String> {
+    let entries = read_manifest(&manifest_path)?;
+    let base_dir = Path::new(&base_dir);
+    Ok(entries
+        .into_iter()
+        .map(|entry| verify_entry(entry, base_dir, Algorithm::Sha256))
+        .collect())
+}
+
+/// Checks a BSD tagged manifest (`SHA256 (file) = hash`), as written by
+/// macOS and the BSDs, using the algorithm named on each line.
+#[tauri::command]
+pub async fn verify_bsd_manifest(
+    manifest_path: String,
+    base_dir: String,
+) -> Result<Vec<VerifyEntry>, String> {
+    let entries = parse_bsd_manifest(&read_manifest_text(&manifest_path)?)?;
+    let base_dir = Path::new(&base_dir);
+    Ok(entries
+        .into_iter()
+        .map(|(algorithm, entry)| verify_entry(entry, base_dir, algorithm))
+        .collect())
+}
+
+/// Returns the deepest directory containing every path.
+fn common_base_dir(paths: &[PathBuf]) -> PathBuf {
+    let mut dirs = paths
+        .iter()
+        .map(|path| path.parent().unwrap_or_else(|| Path::new("")));
+    let Some(first) = dirs.next() else {
+        return PathBuf::new();
+    };
+    dirs.fold(first.to_path_buf(), |base, dir| {
+        base.components()
+            .zip(dir.components())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect()
+    })
+}
+
+/// Renders `path` relative to `base` with forward slashes, as coreutils
+/// expects on every platform.
+pub fn portable_relative_name(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Builds a manifest compatible with `sha256sum -c` (or the matching tool
+/// for `algorithm`). Filenames are relative to the deepest directory that
+/// contains all `paths`, so running the check from there succeeds.
+#[tauri::command]
+pub async fn generate_manifest(paths: Vec<String>, algorithm: String) -> Result<String, String> {
+    let algorithm = Algorithm::from_id(&algorithm)
+        .ok_or_else(|| format!("unsupported algorithm: {}", algorithm))?;
+    let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let base = common_base_dir(&paths);
+
+    let mut manifest = String::new();
+    for path in &paths {
+        let hash = File::open(path)
+            .and_then(|file| algorithms::hash_reader(file, algorithm))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        manifest.push_str(&hash);
+        manifest.push_str("  ");
+        manifest.push_str(&portable_relative_name(path, &base));
+        manifest.push('\n');
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const HASH_A: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    const HASH_B: &str = "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592";
+
+    fn write_manifest(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn path_of(file: &NamedTempFile) -> String {
+        file.path().to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_parse_text_and_binary_conventions() {
+        let entries =
+            parse_manifest(&format!("{}  a.txt\n{} *dir/b.bin\n", HASH_A, HASH_B)).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].filename, "a.txt");
+        assert_eq!(entries[1].filename, "dir/b.bin");
+        assert_eq!(entries[1].hash, HASH_B);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_line() {
+        assert!(parse_manifest("not a manifest line").is_err());
+        assert!(parse_manifest(&format!("{} a.txt", HASH_A)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_equivalent_under_different_names() {
+        let a = write_manifest(&format!(
+            "{}  one.txt\n{}  two.txt\n{}  three.txt\n",
+            HASH_A, HASH_B, HASH_A
+        ));
+        let b = write_manifest(&format!(
+            "{}  mirror/x.txt\r\n{}  mirror/y.txt\r\n{}  other/z.txt\r\n",
+            HASH_B,
+            HASH_A.to_uppercase(),
+            HASH_A
+        ));
+
+        let result = manifests_equivalent_by_content(path_of(&a), path_of(&b)).await;
+        assert_eq!(result, Ok(true));
+    }
+
+    #[tokio::test]
+    async fn test_not_equivalent_when_hash_differs() {
+        let a = write_manifest(&format!("{}  one.txt\n{}  two.txt\n", HASH_A, HASH_B));
+        let b = write_manifest(&format!("{}  one.txt\n{}  two.txt\n", HASH_A, HASH_A));
+
+        let result = manifests_equivalent_by_content(path_of(&a), path_of(&b)).await;
+        assert_eq!(result, Ok(false));
+    }
+
+    #[tokio::test]
+    async fn test_verify_manifest_match_and_mismatch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("empty.txt"), b"").unwrap();
+        fs::write(temp_dir.path().join("fox.bin"), b"not the fox").unwrap();
+        let manifest_path = temp_dir.path().join("SHA256SUMS");
+        fs::write(
+            &manifest_path,
+            format!("{}  empty.txt\n{} *fox.bin\n", HASH_A, HASH_B),
+        )
+        .unwrap();
+
+        let results = verify_manifest(
+            manifest_path.to_string_lossy().to_string(),
+            temp_dir.path().to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].filename, "empty.txt");
+        assert!(results[0].ok);
+        assert_eq!(results[0].actual, HASH_A);
+        assert_eq!(results[1].filename, "fox.bin");
+        assert!(!results[1].ok);
+        assert_eq!(results[1].expected, HASH_B);
+        assert_ne!(results[1].actual, HASH_B);
+    }
+
+    #[tokio::test]
+    async fn test_verify_manifest_missing_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest_path = temp_dir.path().join("SHA256SUMS");
+        fs::write(&manifest_path, format!("{}  gone.txt\n", HASH_A)).unwrap();
+
+        let results = verify_manifest(
+            manifest_path.to_string_lossy().to_string(),
+            temp_dir.path().to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!results[0].ok);
+        assert!(results[0].actual.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_manifest_rejects_paths_outside_base_dir() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let base_dir = temp_dir.path().join("release");
+        fs::create_dir(&base_dir).unwrap();
+        // Would match HASH_A if it were read
+        let outside = temp_dir.path().join("secret.txt");
+        fs::write(&outside, b"").unwrap();
+        fs::write(base_dir.join("empty.txt"), b"").unwrap();
+        let manifest_path = temp_dir.path().join("SHA256SUMS");
+        fs::write(
+            &manifest_path,
+            format!(
+                "{h}  ../secret.txt\n{h}  {}\n{h}  ./empty.txt\n",
+                outside.display(),
+                h = HASH_A
+            ),
+        )
+        .unwrap();
+
+        let results = verify_manifest(
+            manifest_path.to_string_lossy().to_string(),
+            base_dir.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].filename, "../secret.txt");
+        assert!(!results[0].ok);
+        assert!(results[0].actual.is_empty());
+        assert!(!results[1].ok);
+        assert!(results[1].actual.is_empty());
+        assert!(results[2].ok);
+    }
+
+    #[test]
+    fn test_parse_bsd_line() {
+        let (algorithm, entry) =
+            parse_bsd_line(&format!("SHA256 (dir/a (1).txt) = {}", HASH_A)).unwrap();
+        assert_eq!(algorithm, Algorithm::Sha256);
+        assert_eq!(entry.filename, "dir/a (1).txt");
+
+        assert_eq!(
+            parse_bsd_line("SHA3-256 (x) = ab").map(|(a, _)| a),
+            Some(Algorithm::Sha3_256)
+        );
+        assert_eq!(
+            parse_bsd_line("RMD160 (x) = ab").map(|(a, _)| a),
+            Some(Algorithm::Ripemd160)
+        );
+        assert!(parse_bsd_line("CRC32 (x) = ab").is_none());
+        assert!(parse_bsd_line(&format!("{}  a.txt", HASH_A)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_verify_bsd_manifest_mixed_algorithms() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("empty.txt"), b"").unwrap();
+        fs::write(
+            temp_dir.path().join("fox.txt"),
+            b"The quick brown fox jumps over the lazy dog",
+        )
+        .unwrap();
+        let manifest_path = temp_dir.path().join("CHECKSUMS");
+        fs::write(
+            &manifest_path,
+            format!(
+                "MD5 (fox.txt) = 9e107d9d372bb6826bd81d3542a419d6\n\
+                 SHA256 (empty.txt) = {}\n\
+                 SHA256 (fox.txt) = {}\n",
+                HASH_A, HASH_A
+            ),
+        )
+        .unwrap();
+
+        let results = verify_bsd_manifest(
+            manifest_path.to_string_lossy().to_string(),
+            temp_dir.path().to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].algorithm, "md5");
+        assert!(results[0].ok);
+        assert_eq!(results[1].algorithm, "sha256");
+        assert!(results[1].ok);
+        assert!(!results[2].ok);
+        assert_eq!(results[2].actual, HASH_B);
+    }
+
+    #[test]
+    fn test_common_base_dir() {
+        let paths = vec![
+            PathBuf::from("/data/a/one.txt"),
+            PathBuf::from("/data/a/b/two.txt"),
+            PathBuf::from("/data/c/three.txt"),
+        ];
+        assert_eq!(common_base_dir(&paths), PathBuf::from("/data"));
+        assert_eq!(
+            portable_relative_name(&paths[1], Path::new("/data")),
+            "a/b/two.txt"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_manifest_round_trips() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("top.txt"), b"top level").unwrap();
+        fs::write(root.join("sub").join("nested.txt"), b"nested file").unwrap();
+        let paths = vec![
+            root.join("top.txt").to_string_lossy().to_string(),
+            root.join("sub")
+                .join("nested.txt")
+                .to_string_lossy()
+                .to_string(),
+        ];
+
+        let manifest = generate_manifest(paths, "sha256".to_string())
+            .await
+            .unwrap();
+        assert!(manifest.ends_with("  sub/nested.txt\n"));
+        assert!(!manifest.contains('\r'));
+
+        let manifest_path = root.join("SHA256SUMS");
+        fs::write(&manifest_path, &manifest).unwrap();
+        let results = verify_manifest(
+            manifest_path.to_string_lossy().to_string(),
+            root.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|entry| entry.ok));
+    }
+
+    #[tokio::test]
+    async fn test_generate_manifest_errors() {
+        assert!(generate_manifest(vec![], "crc".to_string()).await.is_err());
+        assert!(
+            generate_manifest(vec!["/nonexistent/file".into()], "sha256".to_string())
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            generate_manifest(vec![], "sha256".to_string()).await,
+            Ok(String::new())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_manifest_errors() {
+        let a = write_manifest(&format!("{}  one.txt\n", HASH_A));
+        let result =
+            manifests_equivalent_by_content(path_of(&a), "/nonexistent/manifest".into()).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::algorithms::{Algorithm, ParallelHashers};
+use crate::{
+    apply_digests, fingerprint, is_placeholder_content, sniff_mime_type, throughput_mbps,
+    HashResult, UNKNOWN_MIME_TYPE,
+};
+use std::time::Instant;
+
+/// Incremental hashing into a [`HashResult`] from any source: feed bytes
+/// with [`update`](MultiHasher::update) in as many pieces as needed, then
+/// [`finalize`](MultiHasher::finalize). Large inputs hash each algorithm
+/// on its own thread, as in [`ParallelHashers`].
+///
+/// Besides the digests, the result carries `file_size` (bytes fed), the
+/// placeholder flag, the sniffed MIME type and timing. Path, timestamps and
+/// `metadata_size` are left for the caller.
+pub struct MultiHasher {
+    algorithms: Vec<Algorithm>,
+    hashers: ParallelHashers,
+    bytes: u64,
+    is_empty_or_placeholder: bool,
+    mime_type: Option<String>,
+    started: Instant,
+}
+
+impl MultiHasher {
+    /// Hashes with `algorithms`; commands resolve ids with
+    /// [`parse_selection`](crate::algorithms::parse_selection) first.
+    pub fn with_algorithms(algorithms: &[Algorithm]) -> MultiHasher {
+        MultiHasher {
+            algorithms: algorithms.to_vec(),
+            hashers: ParallelHashers::new(algorithms),
+            bytes: 0,
+            is_empty_or_placeholder: true,
+            mime_type: None,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        self.is_empty_or_placeholder = self.is_empty_or_placeholder && is_placeholder_content(data);
+        // The first piece already holds any magic number, so no extra read
+        self.mime_type.get_or_insert_with(|| sniff_mime_type(data));
+        self.hashers.update(data);
+        self.bytes += data.len() as u64;
+    }
+
+    /// Bytes fed so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    pub fn finalize(self) -> HashResult {
+        let mut result = HashResult {
+            file_size: self.bytes,
+            is_empty: self.bytes == 0,
+            is_empty_or_placeholder: self.is_empty_or_placeholder,
+            mime_type: self
+                .mime_type
+                .unwrap_or_else(|| UNKNOWN_MIME_TYPE.to_string()),
+            ..Default::default()
+        };
+        apply_digests(&mut result, &self.algorithms, self.hashers.finalize());
+        result.fingerprint = fingerprint(&result.sha256);
+        let elapsed = self.started.elapsed();
+        result.duration_ms = elapsed.as_millis() as u64;
+        result.throughput_mbps = throughput_mbps(self.bytes, elapsed);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+
+    const FOX: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn test_pieces_match_single_shot() {
+        let mut whole = MultiHasher::with_algorithms(&Algorithm::DEFAULT);
+        whole.update(FOX);
+        let whole = whole.finalize();
+
+        let mut pieces = MultiHasher::with_algorithms(&Algorithm::DEFAULT);
+        for piece in FOX.chunks(5) {
+            pieces.update(piece);
+            pieces.update(&[]);
+        }
+        assert_eq!(pieces.bytes(), 43);
+        let pieces = pieces.finalize();
+
+        for algorithm in Algorithm::DEFAULT {
+            let expected = algorithms::hash_reader(FOX, algorithm).unwrap();
+            assert_eq!(pieces.clone().digest_mut(algorithm), &expected);
+            assert_eq!(whole.clone().digest_mut(algorithm), &expected);
+        }
+        assert_eq!(pieces.file_size, 43);
+        assert_eq!(pieces.fingerprint, "d7a8 fbb3 07d7 8094");
+        assert!(!pieces.is_empty_or_placeholder);
+        assert_eq!(pieces.mime_type, UNKNOWN_MIME_TYPE);
+    }
+
+    #[test]
+    fn test_selected_algorithms_only() {
+        let mut hasher = MultiHasher::with_algorithms(&[Algorithm::Md5, Algorithm::Sha256]);
+        hasher.update(FOX);
+        let result = hasher.finalize();
+
+        assert_eq!(result.md5, "9e107d9d372bb6826bd81d3542a419d6");
+        assert!(!result.sha256.is_empty());
+        assert!(result.sha1.is_empty());
+        assert!(result.blake3.is_empty());
+    }
+
+    #[test]
+    fn test_nothing_fed() {
+        let result = MultiHasher::with_algorithms(&[Algorithm::Md5]).finalize();
+        assert_eq!(result.md5, "d41d8cd98f00b204e9800998ecf8427e");
+        assert!(result.is_empty_or_placeholder);
+        assert_eq!(result.mime_type, UNKNOWN_MIME_TYPE);
+    }
+}
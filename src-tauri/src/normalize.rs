@@ -0,0 +1,203 @@
+use std::io::{self, Read};
+
+/// How the text is laid out, decided by its byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+    /// UTF-8 or any single-byte encoding.
+    Bytes,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl Layout {
+    fn unit_width(self) -> usize {
+        match self {
+            Layout::Bytes => 1,
+            Layout::Utf16Le | Layout::Utf16Be => 2,
+        }
+    }
+
+    /// Encodes an ASCII control character as one code unit.
+    fn unit(self, ascii: u8) -> Vec<u8> {
+        match self {
+            Layout::Bytes => vec![ascii],
+            Layout::Utf16Le => vec![ascii, 0],
+            Layout::Utf16Be => vec![0, ascii],
+        }
+    }
+}
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Longest BOM, and so how much has to be read before deciding.
+const BOM_PEEK: usize = 3;
+
+/// Wraps a text reader so it yields the content without a leading UTF-8 or
+/// UTF-16 BOM and with CRLF and lone CR line endings turned into LF.
+/// Line endings in UTF-16 are matched per 16-bit code unit, so the encoding
+/// itself is left as it was.
+pub struct TextNormalizer<R> {
+    inner: R,
+    layout: Option<Layout>,
+    /// Bytes read but not yet normalized: a BOM candidate or half a unit.
+    carry: Vec<u8>,
+    /// Normalized bytes waiting to be handed out.
+    out: Vec<u8>,
+    pos: usize,
+    after_cr: bool,
+}
+
+impl<R: Read> TextNormalizer<R> {
+    pub fn new(inner: R) -> TextNormalizer<R> {
+        TextNormalizer {
+            inner,
+            layout: None,
+            carry: Vec::new(),
+            out: Vec::new(),
+            pos: 0,
+            after_cr: false,
+        }
+    }
+
+    /// Reads enough of the start to recognise a BOM, then strips it.
+    fn detect_layout(&mut self) -> io::Result<Layout> {
+        let mut head = [0u8; BOM_PEEK];
+        while self.carry.len() < BOM_PEEK {
+            let n = self.inner.read(&mut head[..BOM_PEEK - self.carry.len()])?;
+            if n == 0 {
+                break;
+            }
+            self.carry.extend_from_slice(&head[..n]);
+        }
+        let (layout, bom_len) = if self.carry.starts_with(UTF8_BOM) {
+            (Layout::Bytes, UTF8_BOM.len())
+        } else if self.carry.starts_with(UTF16LE_BOM) {
+            (Layout::Utf16Le, UTF16LE_BOM.len())
+        } else if self.carry.starts_with(UTF16BE_BOM) {
+            (Layout::Utf16Be, UTF16BE_BOM.len())
+        } else {
+            (Layout::Bytes, 0)
+        };
+        self.carry.drain(..bom_len);
+        Ok(layout)
+    }
+
+    /// Normalizes every whole code unit in `carry`, keeping any trailing
+    /// partial unit for the next read.
+    fn normalize_carry(&mut self, layout: Layout) {
+        let width = layout.unit_width();
+        let (cr, lf) = (layout.unit(b'\r'), layout.unit(b'\n'));
+        let whole = self.carry.len() / width * width;
+        for unit in self.carry[..whole].chunks(width) {
+            if unit == cr.as_slice() {
+                self.out.extend_from_slice(&lf);
+                self.after_cr = true;
+                continue;
+            }
+            let is_crlf = self.after_cr && unit == lf.as_slice();
+            self.after_cr = false;
+            if !is_crlf {
+                self.out.extend_from_slice(unit);
+            }
+        }
+        self.carry.drain(..whole);
+    }
+
+    /// Refills `out`, returning false at the end of the input.
+    fn refill(&mut self) -> io::Result<bool> {
+        self.out.clear();
+        self.pos = 0;
+        let layout = match self.layout {
+            Some(layout) => layout,
+            None => {
+                let layout = self.detect_layout()?;
+                self.layout = Some(layout);
+                layout
+            }
+        };
+
+        let mut chunk = [0u8; 8192];
+        while self.out.is_empty() {
+            let n = self.inner.read(&mut chunk)?;
+            if n == 0 {
+                // A truncated final unit is passed through unchanged
+                self.normalize_carry(layout);
+                self.out.append(&mut self.carry);
+                return Ok(!self.out.is_empty());
+            }
+            self.carry.extend_from_slice(&chunk[..n]);
+            self.normalize_carry(layout);
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for TextNormalizer<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.out.len() && !self.refill()? {
+            return Ok(0);
+        }
+        let n = buf.len().min(self.out.len() - self.pos);
+        buf[..n].copy_from_slice(&self.out[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds the input one byte per read so every boundary case is hit.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.split_first() {
+                Some((&byte, rest)) if !buf.is_empty() => {
+                    buf[0] = byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn normalized(input: &[u8]) -> Vec<u8> {
+        let mut whole = Vec::new();
+        TextNormalizer::new(input).read_to_end(&mut whole).unwrap();
+        let mut trickled = Vec::new();
+        TextNormalizer::new(Trickle(input))
+            .read_to_end(&mut trickled)
+            .unwrap();
+        assert_eq!(whole, trickled);
+        whole
+    }
+
+    #[test]
+    fn test_line_endings() {
+        assert_eq!(normalized(b"a\r\nb\rc\nd\r\n"), b"a\nb\nc\nd\n");
+        assert_eq!(normalized(b"\r\r\n\n"), b"\n\n\n");
+        assert_eq!(normalized(b""), b"");
+    }
+
+    #[test]
+    fn test_utf8_bom_stripped() {
+        assert_eq!(normalized(b"\xEF\xBB\xBFhi\r\n"), b"hi\n");
+        // Only a leading BOM counts
+        assert_eq!(normalized(b"hi\xEF\xBB\xBF"), b"hi\xEF\xBB\xBF");
+        assert_eq!(normalized(b"\xEF\xBB"), b"\xEF\xBB");
+    }
+
+    #[test]
+    fn test_utf16_line_endings_per_unit() {
+        assert_eq!(normalized(b"\xFF\xFEa\0\r\0\n\0"), b"a\0\n\0");
+        assert_eq!(normalized(b"\xFE\xFF\0a\0\r\0\n"), b"\0a\0\n");
+        // A 0x0D byte inside a wider character isn't a CR
+        assert_eq!(normalized(b"\xFF\xFE\x0D\x04"), b"\x0D\x04");
+        assert_eq!(normalized(b"\xFF\xFEa\0b"), b"a\0b");
+    }
+}
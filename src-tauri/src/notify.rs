@@ -0,0 +1,68 @@
+use crate::HashResult;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+/// Hashes quicker than this finish before the user has looked away.
+const NOTIFY_AFTER: Duration = Duration::from_secs(3);
+
+/// Decides whether a finished hash deserves a desktop notification: only
+/// slow ones, and only when the window isn't already on screen.
+fn should_notify(elapsed: Duration, window_visible: bool) -> bool {
+    elapsed >= NOTIFY_AFTER && !window_visible
+}
+
+/// Shows "Checksum complete for <file>" with the SHA-256 prefix when the
+/// hash took long enough that the user has probably switched away.
+pub fn notify_if_slow<R: Runtime>(app: &AppHandle<R>, result: &HashResult, elapsed: Duration) {
+    let window_visible = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+    if !should_notify(elapsed, window_visible) {
+        return;
+    }
+
+    let file_name = Path::new(&result.path)
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let prefix: String = result.sha256.chars().take(16).collect();
+    // A missing notification daemon isn't worth failing the hash over
+    let _ = app
+        .notification()
+        .builder()
+        .title(format!("Checksum complete for {}", file_name))
+        .body(format!("sha256: {}…", prefix))
+        .show();
+}
+
+/// Shows a problem the user should know about, such as a failure during
+/// startup, since a windowed release build has no console for stderr.
+pub fn notify_error<R: Runtime>(app: &AppHandle<R>, message: &str) {
+    // A missing notification daemon leaves nowhere else to report it
+    let _ = app
+        .notification()
+        .builder()
+        .title("Checksum Check")
+        .body(message)
+        .show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_notify_threshold() {
+        assert!(!should_notify(Duration::from_millis(2999), false));
+        assert!(should_notify(Duration::from_secs(3), false));
+        assert!(should_notify(Duration::from_secs(60), false));
+    }
+
+    #[test]
+    fn test_should_not_notify_when_visible() {
+        assert!(!should_notify(Duration::from_secs(60), true));
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+/// Cancellation flags for in-flight hash operations, keyed by the
+/// frontend-supplied operation id. Held in Tauri managed state.
+#[derive(Default)]
+pub struct Operations {
+    flags: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl Operations {
+    /// Starts tracking `id`. The entry is removed again when the returned
+    /// guard is dropped, so finished operations never linger.
+    pub fn register(&self, id: &str) -> OperationGuard<'_> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.flags
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), flag.clone());
+        OperationGuard {
+            operations: self,
+            id: id.to_string(),
+            flag,
+        }
+    }
+
+    /// Signals the operation to stop. Returns false if `id` isn't running.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.flags.lock().unwrap().get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Signals every tracked operation to stop and forgets them, returning
+    /// how many were signalled.
+    pub fn cancel_all(&self) -> usize {
+        let mut flags = self.flags.lock().unwrap();
+        let count = flags.len();
+        for (_, flag) in flags.drain() {
+            flag.store(true, Ordering::SeqCst);
+        }
+        count
+    }
+}
+
+pub struct OperationGuard<'a> {
+    operations: &'a Operations,
+    id: String,
+    flag: Arc<AtomicBool>,
+}
+
+impl OperationGuard<'_> {
+    pub fn flag(&self) -> &AtomicBool {
+        &self.flag
+    }
+}
+
+impl Drop for OperationGuard<'_> {
+    fn drop(&mut self) {
+        let mut flags = self.operations.flags.lock().unwrap();
+        // Only remove our own entry, not one re-registered under the same id
+        if flags
+            .get(&self.id)
+            .is_some_and(|flag| Arc::ptr_eq(flag, &self.flag))
+        {
+            flags.remove(&self.id);
+        }
+    }
+}
+
+/// Returns the error used when a hash is stopped via [`cancel_checksum`].
+pub fn cancelled_error() -> io::Error {
+    io::Error::other("cancelled")
+}
+
+/// Returns an error if `cancel` has been set.
+pub fn check_cancelled(cancel: Option<&AtomicBool>) -> io::Result<()> {
+    match cancel {
+        Some(flag) if flag.load(Ordering::SeqCst) => Err(cancelled_error()),
+        _ => Ok(()),
+    }
+}
+
+/// Requests that the operation started with `op_id` stop at its next chunk.
+#[tauri::command]
+pub fn cancel_checksum(op_id: String, operations: State<'_, Operations>) -> bool {
+    operations.cancel(&op_id)
+}
+
+/// Stops every in-flight hash, for an app-wide stop button. Returns how
+/// many operations were signalled.
+#[tauri::command]
+pub fn cancel_all(operations: State<'_, Operations>) -> usize {
+    operations.cancel_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_sets_flag() {
+        let operations = Operations::default();
+        let guard = operations.register("op-1");
+
+        assert!(!guard.flag().load(Ordering::SeqCst));
+        assert!(operations.cancel("op-1"));
+        assert!(guard.flag().load(Ordering::SeqCst));
+        assert!(check_cancelled(Some(guard.flag())).is_err());
+    }
+
+    #[test]
+    fn test_cancel_unknown_operation() {
+        let operations = Operations::default();
+        assert!(!operations.cancel("missing"));
+    }
+
+    #[test]
+    fn test_guard_unregisters_on_drop() {
+        let operations = Operations::default();
+        drop(operations.register("op-1"));
+
+        assert!(!operations.cancel("op-1"));
+    }
+
+    #[test]
+    fn test_cancel_all() {
+        let operations = Operations::default();
+        let first = operations.register("op-1");
+        let second = operations.register("op-2");
+
+        assert_eq!(operations.cancel_all(), 2);
+        assert!(first.flag().load(Ordering::SeqCst));
+        assert!(second.flag().load(Ordering::SeqCst));
+        // Cleared, so a second stop has nothing left to signal
+        assert_eq!(operations.cancel_all(), 0);
+        drop(first);
+        assert!(!operations.cancel("op-2"));
+    }
+}
@@ -0,0 +1,170 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Longest path the classic Win32 APIs accept without the `\\?\` prefix.
+#[cfg(any(windows, test))]
+const MAX_PATH: usize = 260;
+
+/// Rewrites a Windows path longer than `MAX_PATH` in the extended-length
+/// `\\?\` form (`\\?\UNC\` for network shares). Shorter or already
+/// prefixed paths are returned unchanged.
+#[cfg(any(windows, test))]
+fn extended_length_form(path: &str) -> String {
+    if path.len() < MAX_PATH || path.starts_with(r"\\?\") {
+        return path.to_string();
+    }
+    // The prefix turns off separator normalisation, so only `\` works
+    let path = path.replace('/', r"\");
+    match path.strip_prefix(r"\\") {
+        Some(share) => format!(r"\\?\UNC\{}", share),
+        None => format!(r"\\?\{}", path),
+    }
+}
+
+/// Converts a path from the frontend into one the OS can open, including
+/// paths beyond `MAX_PATH` on Windows.
+pub fn platform_path(path: &str) -> PathBuf {
+    #[cfg(windows)]
+    {
+        PathBuf::from(extended_length_form(path))
+    }
+    #[cfg(not(windows))]
+    {
+        PathBuf::from(path)
+    }
+}
+
+/// Drops the `\\?\` prefix `canonicalize` adds on Windows when the path
+/// is short enough not to need it, so users see `C:\data\file.iso`.
+#[cfg(any(windows, test))]
+fn without_verbatim_prefix(path: &str) -> &str {
+    match path.strip_prefix(r"\\?\") {
+        Some(rest) if rest.len() < MAX_PATH && !rest.starts_with(r"UNC\") => rest,
+        _ => path,
+    }
+}
+
+/// Resolves `path` to an absolute path without `.`, `..` or symlinks, so
+/// two spellings of the same file report the same path. Fails if the path
+/// doesn't exist or a symlink along it is broken.
+pub fn canonical_path(path: &str) -> io::Result<String> {
+    let resolved = fs::canonicalize(platform_path(path))
+        .map_err(|e| io::Error::new(e.kind(), format!("cannot resolve {}: {}", path, e)))?;
+    let resolved = resolved.to_string_lossy();
+    #[cfg(windows)]
+    {
+        Ok(without_verbatim_prefix(&resolved).to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        Ok(resolved.to_string())
+    }
+}
+
+/// Rejects paths that were mangled by a lossy UTF-8 conversion before
+/// reaching the backend; they can never name the intended file.
+pub fn check_utf8(path: &str) -> Result<(), String> {
+    if path.contains(char::REPLACEMENT_CHARACTER) {
+        return Err("path is not valid UTF-8".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_paths_unchanged() {
+        assert_eq!(
+            extended_length_form(r"C:\data\file.iso"),
+            r"C:\data\file.iso"
+        );
+        let prefixed = format!(r"\\?\C:\{}", "a".repeat(300));
+        assert_eq!(extended_length_form(&prefixed), prefixed);
+    }
+
+    #[test]
+    fn test_long_paths_get_prefix() {
+        let deep = format!(r"C:\{}\file.bin", "d".repeat(300));
+        assert_eq!(extended_length_form(&deep), format!(r"\\?\{}", deep));
+
+        let share = format!(r"\\server\share\{}", "s".repeat(300));
+        assert_eq!(
+            extended_length_form(&share),
+            format!(r"\\?\UNC\server\share\{}", "s".repeat(300))
+        );
+    }
+
+    #[test]
+    fn test_without_verbatim_prefix() {
+        assert_eq!(
+            without_verbatim_prefix(r"\\?\C:\data\a.iso"),
+            r"C:\data\a.iso"
+        );
+        assert_eq!(without_verbatim_prefix(r"C:\data\a.iso"), r"C:\data\a.iso");
+        let share = r"\\?\UNC\server\share\a.iso";
+        assert_eq!(without_verbatim_prefix(share), share);
+        let deep = format!(r"\\?\C:\{}", "d".repeat(300));
+        assert_eq!(without_verbatim_prefix(&deep), deep);
+    }
+
+    #[test]
+    fn test_canonical_path_resolves_dot_segments() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = fs::canonicalize(temp_dir.path()).unwrap();
+        fs::create_dir(root.join("subdir")).unwrap();
+        fs::write(root.join("file.txt"), b"data").unwrap();
+
+        let spelled = root.join("subdir").join("..").join(".").join("file.txt");
+        let resolved = canonical_path(&spelled.to_string_lossy()).unwrap();
+        assert_eq!(PathBuf::from(resolved), root.join("file.txt"));
+
+        let missing = root.join("nope.txt").to_string_lossy().to_string();
+        let error = canonical_path(&missing).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().starts_with("cannot resolve "));
+    }
+
+    #[test]
+    fn test_check_utf8() {
+        assert!(check_utf8("/tmp/caf\u{e9}.txt").is_ok());
+        assert!(check_utf8("/tmp/caf\u{fffd}.txt").is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_hash_deep_windows_path() {
+        use crate::algorithms::Algorithm;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut dir = temp_dir.path().to_path_buf();
+        while dir.as_os_str().len() < MAX_PATH {
+            dir.push("nested_directory_level");
+        }
+        let deep = extended_length_form(&dir.to_string_lossy());
+        std::fs::create_dir_all(&deep).unwrap();
+        let file = format!(r"{}\deep.txt", deep);
+        std::fs::write(&file, b"deep").unwrap();
+
+        let plain = format!(r"{}\deep.txt", dir.to_string_lossy());
+        let result = crate::calculate_file_hash(&plain, &Algorithm::ALL).unwrap();
+        assert_eq!(result.file_size, 4);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_hash_prefixed_windows_path() {
+        use crate::algorithms::Algorithm;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("short.txt");
+        std::fs::write(&file, b"short").unwrap();
+        let absolute = std::path::absolute(&file).unwrap();
+        let prefixed = format!(r"\\?\{}", absolute.to_string_lossy());
+
+        let result = crate::calculate_file_hash(&prefixed, &Algorithm::ALL).unwrap();
+        assert_eq!(result.file_size, 5);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::algorithms::{self, Algorithm};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// SHA-256 over the first and last `edge_bytes` of the file followed by its
+/// size as little-endian u64. Files no longer than `2 * edge_bytes` are
+/// hashed whole (plus the size), so the edges never overlap.
+fn edge_fingerprint(path: &str, edge_bytes: u64) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let size_bytes = size.to_le_bytes();
+
+    if size <= edge_bytes.saturating_mul(2) {
+        return algorithms::hash_reader((&mut file).chain(&size_bytes[..]), Algorithm::Sha256);
+    }
+
+    let mut tail = File::open(path)?;
+    tail.seek(SeekFrom::Start(size - edge_bytes))?;
+    let reader = file
+        .take(edge_bytes)
+        .chain(tail.take(edge_bytes))
+        .chain(&size_bytes[..]);
+    algorithms::hash_reader(reader, Algorithm::Sha256)
+}
+
+/// Cheap change detection for huge files: reads only `edge_bytes` from
+/// each end. Edits confined to the middle of the file go unnoticed, and the
+/// value never matches a plain SHA-256 of the file.
+#[tauri::command]
+pub async fn quick_fingerprint(path: String, edge_bytes: u64) -> Result<String, String> {
+    if edge_bytes == 0 {
+        return Err("edge_bytes must be greater than zero".to_string());
+    }
+    edge_fingerprint(&path, edge_bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_file(content: &[u8]) -> (NamedTempFile, String) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_string_lossy().to_string();
+        (file, path)
+    }
+
+    fn sha256(data: &[u8]) -> String {
+        algorithms::hash_reader(data, Algorithm::Sha256).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_large_file_uses_edges_and_size() {
+        let mut content: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+        let (_file, path) = create_test_file(&content);
+
+        let expected = [
+            &content[..4096],
+            &content[content.len() - 4096..],
+            &1_000_000u64.to_le_bytes(),
+        ]
+        .concat();
+        let fingerprint = quick_fingerprint(path, 4096).await.unwrap();
+        assert_eq!(fingerprint, sha256(&expected));
+
+        // A change in the middle isn't seen, one near the end is
+        content[500_000] ^= 0xff;
+        let (_middle, middle_path) = create_test_file(&content);
+        assert_eq!(
+            quick_fingerprint(middle_path, 4096).await.unwrap(),
+            fingerprint
+        );
+        content[999_999] ^= 0xff;
+        let (_tail, tail_path) = create_test_file(&content);
+        assert_ne!(
+            quick_fingerprint(tail_path, 4096).await.unwrap(),
+            fingerprint
+        );
+    }
+
+    #[tokio::test]
+    async fn test_small_file_is_hashed_whole() {
+        let (_file, path) = create_test_file(b"tiny");
+
+        let expected = [&b"tiny"[..], &4u64.to_le_bytes()].concat();
+        assert_eq!(
+            quick_fingerprint(path.clone(), 2).await.unwrap(),
+            sha256(&expected)
+        );
+        assert_eq!(
+            quick_fingerprint(path.clone(), 1024).await.unwrap(),
+            sha256(&expected)
+        );
+        assert!(quick_fingerprint(path, 0).await.is_err());
+    }
+}
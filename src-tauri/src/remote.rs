@@ -0,0 +1,192 @@
+use crate::algorithms::Algorithm;
+use crate::manifest::{self, ManifestEntry, VerifyEntry};
+use reqwest::Url;
+use std::path::Path;
+use std::time::Duration;
+
+/// Give up on a sums file that takes longer than this to download.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest sums file accepted; real ones are a few KiB.
+const MAX_SUMS_BYTES: usize = 1024 * 1024;
+
+/// Accepts only HTTPS, so a network attacker can't swap the published
+/// digests. Plain HTTP is allowed to loopback for local mirrors.
+fn check_url(sums_url: &str) -> Result<Url, String> {
+    let url = Url::parse(sums_url).map_err(|e| format!("{}: {}", sums_url, e))?;
+    let loopback = matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+    match url.scheme() {
+        "https" => Ok(url),
+        "http" if loopback => Ok(url),
+        _ => Err(format!("{}: checksum URLs must use https", sums_url)),
+    }
+}
+
+async fn fetch_sums(url: Url) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let too_large = || "checksum file is too large".to_string();
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_SUMS_BYTES as u64)
+    {
+        return Err(too_large());
+    }
+    // The length header may be missing or wrong, so stop reading as soon
+    // as the body passes the limit rather than buffering all of it
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if body.len() + chunk.len() > MAX_SUMS_BYTES {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    String::from_utf8(body).map_err(|_| "checksum file is not UTF-8".to_string())
+}
+
+/// Finds the line for `file_name`, matching on the last path component so
+/// `./dist/app.iso` in the sums file matches a local `app.iso`. Lines that
+/// aren't entries (such as a PGP signature block) are skipped.
+fn find_entry(sums: &str, file_name: &str) -> Option<ManifestEntry> {
+    sums.lines()
+        .filter_map(|line| manifest::parse_line(line.trim_end_matches('\r')))
+        .find(|entry| {
+            Path::new(&entry.filename).file_name() == Some(std::ffi::OsStr::new(file_name))
+        })
+}
+
+/// Downloads a `SHA256SUMS` file and checks the local file against the
+/// line with its name.
+#[tauri::command]
+pub async fn verify_against_url(path: String, sums_url: String) -> Result<VerifyEntry, String> {
+    let url = check_url(&sums_url)?;
+    let file_name = Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("{}: not a file path", path))?;
+    let sums = fetch_sums(url).await?;
+    let entry = find_entry(&sums, &file_name)
+        .ok_or_else(|| format!("{} is not listed in {}", file_name, sums_url))?;
+    Ok(manifest::verify_path(
+        Path::new(&path),
+        entry,
+        Algorithm::Sha256,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use tempfile::TempDir;
+
+    const FOX_SHA256: &str = "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592";
+
+    /// Serves `body` to a single request and returns the URL to fetch it.
+    fn serve_once(body: String) -> String {
+        serve(body, true)
+    }
+
+    /// Like [`serve_once`], optionally leaving out `Content-Length` so the
+    /// client only learns the size by reading to the end.
+    fn serve(body: String, content_length: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let mut stream = reader.into_inner();
+            let length = if content_length {
+                format!("Content-Length: {}\r\n", body.len())
+            } else {
+                String::new()
+            };
+            // The client may hang up early on an oversized body
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\n{}Connection: close\r\n\r\n{}",
+                length, body
+            );
+        });
+        format!("http://127.0.0.1:{}/SHA256SUMS", port)
+    }
+
+    fn create_fox(temp_dir: &TempDir) -> String {
+        let path = temp_dir.path().join("fox.txt");
+        fs::write(&path, b"The quick brown fox jumps over the lazy dog").unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_check_url_requires_https() {
+        assert!(check_url("https://example.com/SHA256SUMS").is_ok());
+        assert!(check_url("http://localhost:8000/SHA256SUMS").is_ok());
+        assert!(check_url("http://example.com/SHA256SUMS").is_err());
+        assert!(check_url("ftp://example.com/SHA256SUMS").is_err());
+        assert!(check_url("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_against_url_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_fox(&temp_dir);
+        let url = serve_once(format!(
+            "-----BEGIN PGP SIGNED MESSAGE-----\n{}  other.txt\n{} *./dist/fox.txt\n",
+            "0".repeat(64),
+            FOX_SHA256
+        ));
+
+        let entry = verify_against_url(path, url).await.unwrap();
+        assert!(entry.ok);
+        assert_eq!(entry.filename, "./dist/fox.txt");
+        assert_eq!(entry.actual, FOX_SHA256);
+    }
+
+    #[tokio::test]
+    async fn test_verify_against_url_mismatch_and_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_fox(&temp_dir);
+
+        let url = serve_once(format!("{}  fox.txt\n", "0".repeat(64)));
+        let entry = verify_against_url(path.clone(), url).await.unwrap();
+        assert!(!entry.ok);
+
+        let url = serve_once(format!("{}  other.txt\n", FOX_SHA256));
+        assert!(verify_against_url(path, url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_sums_file_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = create_fox(&temp_dir);
+        let line = format!("{}  fox.txt\n", FOX_SHA256);
+        let body = line.repeat(MAX_SUMS_BYTES / line.len() + 1);
+
+        let url = serve(body.clone(), false);
+        assert_eq!(
+            verify_against_url(path.clone(), url).await.unwrap_err(),
+            "checksum file is too large"
+        );
+
+        let url = serve(body, true);
+        assert_eq!(
+            verify_against_url(path, url).await.unwrap_err(),
+            "checksum file is too large"
+        );
+    }
+}
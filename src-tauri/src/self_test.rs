@@ -0,0 +1,138 @@
+use crate::algorithms::{self, Algorithm};
+
+/// Published digests of `""` and `"abc"`: FIPS 180/202 examples for SHA,
+/// the RFC or reference-implementation vectors for the rest.
+fn known_vectors(algorithm: Algorithm) -> [&'static str; 2] {
+    match algorithm {
+        Algorithm::Md5 => [
+            "d41d8cd98f00b204e9800998ecf8427e",
+            "900150983cd24fb0d6963f7d28e17f72",
+        ],
+        Algorithm::Sha1 => [
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+        ],
+        Algorithm::Sha224 => [
+            "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+        ],
+        Algorithm::Sha256 => [
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ],
+        Algorithm::Sha384 => [
+            "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da\
+             274edebfe76f65fbd51ad2f14898b95b",
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+             8086072ba1e7cc2358baeca134c825a7",
+        ],
+        Algorithm::Sha512 => [
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+        ],
+        Algorithm::Blake3 => [
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+        ],
+        Algorithm::Sha3_256 => [
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+        ],
+        Algorithm::Sha3_512 => [
+            "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a6\
+             15b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26",
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e\
+             10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0",
+        ],
+        Algorithm::Xxh3 => ["2d06800538d394c2", "78af5f94892f3950"],
+        Algorithm::Adler32 => ["00000001", "024d0127"],
+        Algorithm::Ripemd160 => [
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31",
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+        ],
+        Algorithm::Whirlpool => [
+            "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7\
+             3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+            "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c\
+             7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+        ],
+        Algorithm::Tiger => [
+            "3293ac630c13f0245f92bbb1766e16167a4e58492dde73f3",
+            "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93",
+        ],
+        Algorithm::Gost94 => [
+            "ce85b99cc46752fffee35cab9a7b0278abb4c2d2055cff685af4912c49490f8d",
+            "f3134348c44fb1b2a277729e2285ebb5cb5e0f29c975bc753b70497c06a4d51d",
+        ],
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct SelfTestResult {
+    pub algorithm: String,
+    pub passed: bool,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct SelfTestReport {
+    /// True when every algorithm passed.
+    pub passed: bool,
+    pub results: Vec<SelfTestResult>,
+}
+
+fn check(algorithm: Algorithm) -> bool {
+    [&b""[..], &b"abc"[..]]
+        .into_iter()
+        .zip(known_vectors(algorithm))
+        .all(|(input, expected)| {
+            algorithms::hash_reader(input, algorithm).is_ok_and(|digest| digest == expected)
+        })
+}
+
+/// Hashes the standard test inputs with every algorithm and compares the
+/// published digests. A failure points at a broken dependency or a CPU
+/// feature bug rather than anything about the user's files.
+#[tauri::command]
+pub fn run_self_test() -> SelfTestReport {
+    let results: Vec<SelfTestResult> = Algorithm::ALL
+        .into_iter()
+        .map(|algorithm| SelfTestResult {
+            algorithm: algorithm.id().to_string(),
+            passed: check(algorithm),
+        })
+        .collect();
+    SelfTestReport {
+        passed: results.iter().all(|result| result.passed),
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_passes() {
+        let report = run_self_test();
+        let failed: Vec<&str> = report
+            .results
+            .iter()
+            .filter(|result| !result.passed)
+            .map(|result| result.algorithm.as_str())
+            .collect();
+        assert!(failed.is_empty(), "failed: {:?}", failed);
+        assert!(report.passed);
+        assert_eq!(report.results.len(), Algorithm::ALL.len());
+    }
+
+    #[test]
+    fn test_vectors_match_hex_length() {
+        for algorithm in Algorithm::ALL {
+            for vector in known_vectors(algorithm) {
+                assert_eq!(vector.len(), algorithm.hex_length(), "{}", algorithm.id());
+            }
+        }
+    }
+}
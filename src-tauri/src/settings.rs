@@ -0,0 +1,270 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Outer position of the main window in physical pixels.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WindowPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// User preferences kept as JSON in the app config directory. Missing
+/// fields fall back to their defaults so older files keep loading.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// Where the main window was when it was last closed.
+    pub window_position: Option<WindowPosition>,
+    /// Global shortcut that shows or hides the main window, in the
+    /// `Ctrl+Shift+H` form. `None` uses [`DEFAULT_HOTKEY`].
+    pub hotkey: Option<String>,
+    /// Launch with only the tray icon instead of showing the window.
+    pub start_hidden: bool,
+    /// Fraction of the monitor height the window is moved up from center on
+    /// first launch. `None` uses [`DEFAULT_WINDOW_OFFSET`].
+    pub window_offset_percent: Option<f64>,
+    /// Left-clicking the tray re-hashes the last file instead of toggling
+    /// the window, for watching a file that is being rebuilt.
+    pub tray_rehash_on_click: bool,
+    /// Write a JSON line per hash (path, algorithms, duration, outcome) to
+    /// a daily log in the app log directory. Read at startup.
+    pub audit_log: bool,
+}
+
+pub const DEFAULT_HOTKEY: &str = "Ctrl+Shift+H";
+
+pub const DEFAULT_WINDOW_OFFSET: f64 = 0.20;
+
+/// Largest offset allowed; beyond this the window rides off the top.
+const MAX_WINDOW_OFFSET: f64 = 0.5;
+
+impl Settings {
+    pub fn hotkey(&self) -> &str {
+        self.hotkey.as_deref().unwrap_or(DEFAULT_HOTKEY)
+    }
+
+    /// The configured offset clamped to `0.0..=0.5`.
+    pub fn window_offset_percent(&self) -> f64 {
+        clamp_offset(self.window_offset_percent.unwrap_or(DEFAULT_WINDOW_OFFSET))
+    }
+}
+
+fn clamp_offset(percent: f64) -> f64 {
+    if percent.is_nan() {
+        return DEFAULT_WINDOW_OFFSET;
+    }
+    percent.clamp(0.0, MAX_WINDOW_OFFSET)
+}
+
+/// Returns the y coordinate after moving a centered window at `window_y` up
+/// by `percent` of the monitor height, never above the monitor's top edge.
+pub fn offset_window_y(window_y: i32, monitor_top: i32, monitor_height: u32, percent: f64) -> i32 {
+    let offset = (monitor_height as f64 * percent) as i32;
+    (window_y - offset).max(monitor_top)
+}
+
+pub fn settings_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(SETTINGS_FILE))
+        .map_err(|e| e.to_string())
+}
+
+/// Reads the settings file, treating a missing or unreadable file as
+/// "nothing saved yet".
+pub fn load(path: &Path) -> Settings {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(path: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+/// Loads the settings, applies `change` and writes them back, so each
+/// caller only touches the fields it owns.
+pub fn update(path: &Path, change: impl FnOnce(&mut Settings)) -> Result<(), String> {
+    let mut settings = load(path);
+    change(&mut settings);
+    save(path, &settings)
+}
+
+/// Moves a window of `window_size` at `position` so it lies fully inside the
+/// monitor at `monitor_origin` with `monitor_size`, keeping the top-left
+/// corner on screen if the window is larger than the monitor.
+pub fn clamp_to_monitor(
+    position: WindowPosition,
+    window_size: (u32, u32),
+    monitor_origin: (i32, i32),
+    monitor_size: (u32, u32),
+) -> WindowPosition {
+    let clamp_axis = |value: i32, window: u32, origin: i32, monitor: u32| {
+        let max = origin + monitor.saturating_sub(window) as i32;
+        value.clamp(origin, max.max(origin))
+    };
+    WindowPosition {
+        x: clamp_axis(position.x, window_size.0, monitor_origin.0, monitor_size.0),
+        y: clamp_axis(position.y, window_size.1, monitor_origin.1, monitor_size.1),
+    }
+}
+
+/// Persists whether the next launch should start hidden in the tray.
+#[tauri::command]
+pub async fn set_start_hidden<R: Runtime>(app: AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let path = settings_path(&app)?;
+    update(&path, |settings| settings.start_hidden = enabled)
+}
+
+/// Persists the first-launch vertical offset, clamped to `0.0..=0.5`.
+#[tauri::command]
+pub async fn set_window_offset<R: Runtime>(app: AppHandle<R>, percent: f64) -> Result<(), String> {
+    let path = settings_path(&app)?;
+    update(&path, |settings| {
+        settings.window_offset_percent = Some(clamp_offset(percent))
+    })
+}
+
+/// Persists whether a tray left-click re-hashes the last file.
+#[tauri::command]
+pub async fn set_tray_rehash_on_click<R: Runtime>(
+    app: AppHandle<R>,
+    enabled: bool,
+) -> Result<(), String> {
+    let path = settings_path(&app)?;
+    update(&path, |settings| settings.tray_rehash_on_click = enabled)
+}
+
+/// Persists whether hashes are written to the audit log. Takes effect on
+/// the next launch.
+#[tauri::command]
+pub async fn set_audit_log<R: Runtime>(app: AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let path = settings_path(&app)?;
+    update(&path, |settings| settings.audit_log = enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_file_is_default() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(
+            load(&temp_dir.path().join("nope.json")),
+            Settings::default()
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config").join(SETTINGS_FILE);
+
+        update(&path, |settings| {
+            settings.window_position = Some(WindowPosition { x: 40, y: -10 });
+        })
+        .unwrap();
+
+        assert_eq!(
+            load(&path).window_position,
+            Some(WindowPosition { x: 40, y: -10 })
+        );
+    }
+
+    #[test]
+    fn test_hotkey_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        assert_eq!(load(&path).hotkey(), DEFAULT_HOTKEY);
+
+        fs::write(&path, r#"{ "hotkey": "Alt+F9" }"#).unwrap();
+        assert_eq!(load(&path).hotkey(), "Alt+F9");
+    }
+
+    #[test]
+    fn test_start_hidden_defaults_off() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        fs::write(&path, r#"{ "hotkey": "Alt+F9" }"#).unwrap();
+        assert!(!load(&path).start_hidden);
+
+        update(&path, |settings| settings.start_hidden = true).unwrap();
+        let reloaded = load(&path);
+        assert!(reloaded.start_hidden);
+        assert_eq!(reloaded.hotkey(), "Alt+F9");
+    }
+
+    #[test]
+    fn test_tray_rehash_defaults_off() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        assert!(!load(&path).tray_rehash_on_click);
+
+        update(&path, |settings| settings.tray_rehash_on_click = true).unwrap();
+        assert!(load(&path).tray_rehash_on_click);
+    }
+
+    #[test]
+    fn test_window_offset_clamped() {
+        let mut settings = Settings::default();
+        assert_eq!(settings.window_offset_percent(), DEFAULT_WINDOW_OFFSET);
+
+        settings.window_offset_percent = Some(0.9);
+        assert_eq!(settings.window_offset_percent(), 0.5);
+        settings.window_offset_percent = Some(-1.0);
+        assert_eq!(settings.window_offset_percent(), 0.0);
+        settings.window_offset_percent = Some(f64::NAN);
+        assert_eq!(settings.window_offset_percent(), DEFAULT_WINDOW_OFFSET);
+    }
+
+    #[test]
+    fn test_offset_window_y_stays_on_screen() {
+        assert_eq!(offset_window_y(240, 0, 1080, 0.20), 24);
+        // A short monitor would push the top edge above the screen
+        assert_eq!(offset_window_y(100, 0, 1080, 0.5), 0);
+        // Monitors stacked above the primary have a negative top
+        assert_eq!(offset_window_y(-800, -1080, 1080, 0.5), -1080);
+    }
+
+    #[test]
+    fn test_load_ignores_garbage() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SETTINGS_FILE);
+        fs::write(&path, "not json").unwrap();
+
+        assert_eq!(load(&path), Settings::default());
+    }
+
+    #[test]
+    fn test_clamp_to_monitor() {
+        let inside = WindowPosition { x: 100, y: 100 };
+        assert_eq!(
+            clamp_to_monitor(inside, (800, 600), (0, 0), (1920, 1080)),
+            inside
+        );
+
+        // Saved on a second monitor that has since been unplugged
+        let off_screen = WindowPosition { x: 3000, y: -500 };
+        assert_eq!(
+            clamp_to_monitor(off_screen, (800, 600), (0, 0), (1920, 1080)),
+            WindowPosition { x: 1120, y: 0 }
+        );
+
+        // Window bigger than the monitor stays pinned to its origin
+        assert_eq!(
+            clamp_to_monitor(off_screen, (2000, 1200), (0, 0), (1920, 1080)),
+            WindowPosition { x: 0, y: 0 }
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::algorithms::{self, Algorithm, Hasher};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// Where content with SHA-256 `hash` lives in the store: sharded by the
+/// first two hex characters so no single directory grows huge.
+fn content_path(store_dir: &Path, hash: &str) -> PathBuf {
+    store_dir.join(&hash[..2]).join(hash)
+}
+
+fn store(src: &Path, store_dir: &Path) -> Result<PathBuf, String> {
+    let file = File::open(src).map_err(|e| format!("{}: {}", src.display(), e))?;
+    let store_error = |e: io::Error| {
+        format!(
+            "cannot store {} in {}: {}",
+            src.display(),
+            store_dir.display(),
+            e
+        )
+    };
+    fs::create_dir_all(store_dir).map_err(store_error)?;
+
+    // Hash the bytes while copying them, so what lands in the store is
+    // exactly what was hashed even if `src` changes part-way through. The
+    // uniquely named temporary file is deleted if anything fails, and never
+    // sits at a path that claims to hold a hash
+    let mut partial = NamedTempFile::new_in(store_dir).map_err(store_error)?;
+    let mut hasher = Hasher::new(Algorithm::Sha256);
+    algorithms::try_for_each_chunk(file, |chunk| {
+        hasher.update(chunk);
+        partial.write_all(chunk)
+    })
+    .map_err(store_error)?;
+    let dest = content_path(store_dir, &algorithms::hex_lower(&hasher.finalize()));
+    if dest.exists() {
+        return Ok(dest);
+    }
+
+    let shard = dest.parent().expect("content path has a shard directory");
+    fs::create_dir_all(shard).map_err(store_error)?;
+    partial.persist(&dest).map_err(|e| store_error(e.error))?;
+    Ok(dest)
+}
+
+/// Copies `src` into a content-addressed store at
+/// `store_dir/<first two hex>/<sha256>` and returns that path. Content
+/// already in the store is left as it is.
+#[tauri::command]
+pub async fn store_content_addressed(src: String, store_dir: String) -> Result<String, String> {
+    store(Path::new(&src), Path::new(&store_dir)).map(|dest| dest.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FOX_SHA256: &str = "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592";
+
+    fn fox_file(dir: &TempDir, name: &str) -> String {
+        let path = dir.path().join(name);
+        fs::write(&path, "The quick brown fox jumps over the lazy dog").unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_store_copies_to_sharded_path() {
+        let src_dir = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let src = fox_file(&src_dir, "fox.txt");
+
+        let dest = store_content_addressed(src.clone(), store_dir.path().to_string_lossy().into())
+            .await
+            .unwrap();
+        let expected = store_dir.path().join("d7").join(FOX_SHA256);
+        assert_eq!(Path::new(&dest), expected);
+        assert_eq!(fs::read(&dest).unwrap(), fs::read(&src).unwrap());
+        // The source is copied, not moved
+        assert!(Path::new(&src).exists());
+        assert_eq!(
+            fs::read_dir(store_dir.path().join("d7")).unwrap().count(),
+            1
+        );
+        // No temporary copy is left behind next to the shards
+        assert_eq!(fs::read_dir(store_dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_skips_existing_content() {
+        let src_dir = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store_path = store_dir.path().to_string_lossy().to_string();
+
+        let first = store_content_addressed(fox_file(&src_dir, "a.txt"), store_path.clone())
+            .await
+            .unwrap();
+        // Mark the stored copy so a second copy would be noticed
+        let marker = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1);
+        File::options()
+            .write(true)
+            .open(&first)
+            .unwrap()
+            .set_modified(marker)
+            .unwrap();
+
+        let second = store_content_addressed(fox_file(&src_dir, "b.txt"), store_path)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(fs::metadata(&second).unwrap().modified().unwrap(), marker);
+    }
+
+    #[tokio::test]
+    async fn test_store_missing_source() {
+        let store_dir = TempDir::new().unwrap();
+        let result = store_content_addressed(
+            "/nonexistent/file".into(),
+            store_dir.path().to_string_lossy().into(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
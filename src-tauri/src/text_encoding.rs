@@ -0,0 +1,77 @@
+use encoding_rs::WINDOWS_1252;
+
+/// Encodes `text` as `encoding` (`utf-8`, `utf-16le`, `utf-16be`,
+/// `latin-1` or `windows-1252`, ignoring case) so it can be hashed the way
+/// another tool stored it. Single-byte encodings fail on characters they
+/// can't represent rather than substituting them.
+pub fn encode(text: &str, encoding: &str) -> Result<Vec<u8>, String> {
+    match encoding.trim().to_ascii_lowercase().as_str() {
+        "utf-8" | "utf8" => Ok(text.as_bytes().to_vec()),
+        // encoding_rs only decodes UTF-16, so these are done by hand
+        "utf-16le" => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        "utf-16be" => Ok(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        // True ISO-8859-1, as Python and iconv encode it: every code point
+        // up to U+00FF is one byte. encoding_rs treats the label as 1252
+        "latin-1" | "latin1" | "iso-8859-1" => text
+            .chars()
+            .map(u8::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(|_| "text has characters that latin-1 can't represent".to_string()),
+        "windows-1252" | "cp1252" => {
+            let (bytes, _, had_errors) = WINDOWS_1252.encode(text);
+            if had_errors {
+                return Err("text has characters that windows-1252 can't represent".to_string());
+            }
+            Ok(bytes.into_owned())
+        }
+        other => Err(format!(
+            "unsupported encoding: {} (expected utf-8, utf-16le, utf-16be, latin-1 or windows-1252)",
+            other
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_utf16() {
+        assert_eq!(
+            encode("Aé", "utf-16le").unwrap(),
+            vec![0x41, 0x00, 0xe9, 0x00]
+        );
+        assert_eq!(
+            encode("Aé", "UTF-16BE").unwrap(),
+            vec![0x00, 0x41, 0x00, 0xe9]
+        );
+        // Outside the BMP: a surrogate pair
+        assert_eq!(
+            encode("😀", "utf-16le").unwrap(),
+            vec![0x3d, 0xd8, 0x00, 0xde]
+        );
+    }
+
+    #[test]
+    fn test_encode_latin1() {
+        assert_eq!(encode("café", "latin-1").unwrap(), b"caf\xe9".to_vec());
+        assert_eq!(encode("ÿ", "ISO-8859-1").unwrap(), vec![0xff]);
+        // C1 controls are single bytes in ISO-8859-1
+        assert_eq!(encode("\u{80}\u{9f}", "latin-1").unwrap(), vec![0x80, 0x9f]);
+        assert!(encode("€", "latin-1").is_err());
+        assert!(encode("日本", "latin-1").is_err());
+    }
+
+    #[test]
+    fn test_encode_windows_1252() {
+        assert_eq!(encode("café", "windows-1252").unwrap(), b"caf\xe9".to_vec());
+        assert_eq!(encode("€", "cp1252").unwrap(), vec![0x80]);
+        assert!(encode("日本", "windows-1252").is_err());
+    }
+
+    #[test]
+    fn test_encode_unknown() {
+        assert_eq!(encode("abc", "utf-8").unwrap(), b"abc".to_vec());
+        assert!(encode("abc", "ebcdic").is_err());
+    }
+}
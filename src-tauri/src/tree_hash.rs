@@ -0,0 +1,157 @@
+use crate::algorithms::{self, Algorithm};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::thread;
+
+/// Upper bound on `parts`, since each part is hashed on its own thread.
+const MAX_PARTS: usize = 256;
+
+/// Result of a tree hash over a file split into contiguous ranges.
+///
+/// The root is NOT the plain digest of the file: it is the digest of the
+/// concatenated raw part digests, so it only matches another tree hash
+/// computed with the same algorithm and part count.
+#[derive(serde::Serialize)]
+pub struct TreeHashResult {
+    pub algorithm: String,
+    pub file_size: u64,
+    /// Hex digest of each range, in file order.
+    pub part_hashes: Vec<String>,
+    pub root: String,
+}
+
+/// Splits `file_size` into `parts` contiguous `(offset, length)` ranges.
+/// Never produces more ranges than there are bytes, except for an empty
+/// file which gets a single empty range.
+fn split_ranges(file_size: u64, parts: usize) -> Vec<(u64, u64)> {
+    let parts = (parts as u64).min(file_size).max(1);
+    (0..parts)
+        .map(|i| {
+            let start = i * file_size / parts;
+            let end = (i + 1) * file_size / parts;
+            (start, end - start)
+        })
+        .collect()
+}
+
+fn hash_range(path: &str, offset: u64, length: u64, algorithm: Algorithm) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    algorithms::hash_reader_raw(file.take(length), algorithm)
+}
+
+fn tree_hash(path: &str, algorithm: Algorithm, parts: usize) -> io::Result<TreeHashResult> {
+    let file_size = std::fs::metadata(path)?.len();
+    let ranges = split_ranges(file_size, parts);
+
+    // Each range is read through its own file handle so the seeks don't race
+    let part_digests = thread::scope(|scope| {
+        let handles: Vec<_> = ranges
+            .iter()
+            .map(|&(offset, length)| {
+                scope.spawn(move || hash_range(path, offset, length, algorithm))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("range hashing thread panicked"))
+            .collect::<io::Result<Vec<_>>>()
+    })?;
+
+    let root = algorithms::hash_reader(part_digests.concat().as_slice(), algorithm)?;
+
+    Ok(TreeHashResult {
+        algorithm: algorithm.id().to_string(),
+        file_size,
+        part_hashes: part_digests
+            .iter()
+            .map(|digest| algorithms::hex_lower(digest))
+            .collect(),
+        root,
+    })
+}
+
+/// Hashes `parts` ranges of the file concurrently and combines them into a
+/// root digest. Faster than a plain hash on fast storage, but produces a
+/// different value.
+#[tauri::command]
+pub async fn calculate_tree_hash(
+    path: String,
+    algorithm: String,
+    parts: usize,
+) -> Result<TreeHashResult, String> {
+    let algorithm = Algorithm::from_id(&algorithm)
+        .ok_or_else(|| format!("unsupported algorithm: {}", algorithm))?;
+    if parts == 0 || parts > MAX_PARTS {
+        return Err(format!("parts must be between 1 and {}", MAX_PARTS));
+    }
+    tree_hash(&path, algorithm, parts).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_file(content: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_split_ranges_cover_file() {
+        let ranges = split_ranges(10, 3);
+        assert_eq!(ranges, vec![(0, 3), (3, 3), (6, 4)]);
+
+        // More parts than bytes collapses to one byte per part
+        assert_eq!(split_ranges(2, 8).len(), 2);
+        assert_eq!(split_ranges(0, 4), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_root_is_stable() {
+        let content: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let file = create_test_file(&content);
+        let path = file.path().to_str().unwrap();
+
+        let first = tree_hash(path, Algorithm::Sha256, 4).unwrap();
+        let second = tree_hash(path, Algorithm::Sha256, 4).unwrap();
+
+        assert_eq!(first.part_hashes.len(), 4);
+        assert_eq!(first.root, second.root);
+        assert_eq!(first.part_hashes, second.part_hashes);
+    }
+
+    #[test]
+    fn test_single_part_hashes_part_digest() {
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let file = create_test_file(content);
+        let result = tree_hash(file.path().to_str().unwrap(), Algorithm::Sha256, 1).unwrap();
+
+        assert_eq!(
+            result.part_hashes,
+            vec!["d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"]
+        );
+        let part_digest = algorithms::hash_reader_raw(&content[..], Algorithm::Sha256).unwrap();
+        let expected_root =
+            algorithms::hash_reader(part_digest.as_slice(), Algorithm::Sha256).unwrap();
+        assert_eq!(result.root, expected_root);
+    }
+
+    #[tokio::test]
+    async fn test_command_rejects_bad_arguments() {
+        let file = create_test_file(b"data");
+        let path = file.path().to_string_lossy().to_string();
+
+        assert!(calculate_tree_hash(path.clone(), "sha256".into(), 0)
+            .await
+            .is_err());
+        assert!(calculate_tree_hash(path.clone(), "crc".into(), 2)
+            .await
+            .is_err());
+        assert!(calculate_tree_hash(path, "md5".into(), 2).await.is_ok());
+    }
+}
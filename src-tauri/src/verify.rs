@@ -0,0 +1,177 @@
+use crate::algorithms::{self, Algorithm};
+use std::fs::File;
+use subtle::ConstantTimeEq;
+
+#[derive(serde::Serialize, Debug, PartialEq)]
+pub struct VerifyMatch {
+    pub matched: bool,
+    /// Id of the matching algorithm, or `None` when nothing matched.
+    pub algorithm: Option<String>,
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// Compares two hex digests in time that depends only on their length, so
+/// checking a MAC doesn't reveal how many leading bytes were right. Case is
+/// ignored; anything that isn't valid hex never matches.
+pub(crate) fn constant_time_eq(a: &str, b: &str) -> bool {
+    match (decode_hex(a), decode_hex(b)) {
+        (Some(a), Some(b)) => a.ct_eq(&b).into(),
+        _ => false,
+    }
+}
+
+/// Normalises a pasted digest: surrounding whitespace trimmed, lowercased.
+/// Returns `None` when what's left isn't hex.
+fn normalize_expected(expected: &str) -> Option<String> {
+    let expected = expected.trim().to_ascii_lowercase();
+    let is_hex = !expected.is_empty() && expected.bytes().all(|b| b.is_ascii_hexdigit());
+    is_hex.then_some(expected)
+}
+
+/// Hashes `path` with every algorithm whose digest is as long as
+/// `expected` and returns the first that matches.
+fn find_match(path: &str, expected: &str) -> Result<Option<Algorithm>, String> {
+    let Some(expected) = normalize_expected(expected) else {
+        return Ok(None);
+    };
+    let candidates: Vec<Algorithm> = Algorithm::ALL
+        .into_iter()
+        .filter(|algorithm| algorithm.hex_length() == expected.len())
+        .collect();
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+    let (_, digests) = algorithms::hash_reader_multi_raw(file, &candidates)
+        .map_err(|e| format!("{}: {}", path, e))?;
+    Ok(candidates
+        .into_iter()
+        .zip(digests)
+        .find(|(_, digest)| constant_time_eq(&algorithms::hex_lower(digest), &expected))
+        .map(|(algorithm, _)| algorithm))
+}
+
+/// Checks a pasted digest against the file without needing to know which
+/// algorithm produced it. Only algorithms with a digest of the pasted
+/// length are computed, and the file is read once for all of them.
+#[tauri::command]
+pub async fn verify_any(path: String, expected: String) -> Result<VerifyMatch, String> {
+    let algorithm = find_match(&path, &expected)?;
+    Ok(VerifyMatch {
+        matched: algorithm.is_some(),
+        algorithm: algorithm.map(|algorithm| algorithm.id().to_string()),
+    })
+}
+
+/// Checks a file's length against a published size using metadata only,
+/// so a truncated download shows up before a slow full hash.
+#[tauri::command]
+pub async fn verify_size(path: String, expected_size: u64) -> Result<bool, String> {
+    let metadata = std::fs::metadata(&path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(metadata.len() == expected_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn fox_file(temp_dir: &TempDir) -> String {
+        let path = temp_dir.path().join("fox.txt");
+        fs::write(&path, "The quick brown fox jumps over the lazy dog").unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("d7a8fbb3", "d7a8fbb3"));
+        assert!(constant_time_eq("d7a8fbb3", "D7A8FBB3"));
+        assert!(!constant_time_eq("d7a8fbb3", "d7a8fbb4"));
+        assert!(!constant_time_eq("d7a8fbb3", "d7a8fb"));
+        assert!(!constant_time_eq("d7a8fbb3", "d7a8fbb30"));
+        assert!(!constant_time_eq("zz", "zz"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_any_matches_sha256() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = fox_file(&temp_dir);
+
+        let result = verify_any(
+            path,
+            "  D7A8FBB307D7809469CA9ABCB0082E4F8D5651E46D3CDB762D02D0BF37C9E592\n".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            result,
+            VerifyMatch {
+                matched: true,
+                algorithm: Some("sha256".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_any_matches_md5() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = fox_file(&temp_dir);
+
+        let result = verify_any(path, "9e107d9d372bb6826bd81d3542a419d6".to_string())
+            .await
+            .unwrap();
+        assert_eq!(result.algorithm.as_deref(), Some("md5"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_any_wrong_value() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = fox_file(&temp_dir);
+
+        let wrong = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        for expected in [wrong, "not a hash", ""] {
+            let result = verify_any(path.clone(), expected.to_string())
+                .await
+                .unwrap();
+            assert_eq!(
+                result,
+                VerifyMatch {
+                    matched: false,
+                    algorithm: None,
+                }
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_any_missing_file() {
+        let result = verify_any(
+            "/nonexistent/file.bin".to_string(),
+            "9e107d9d372bb6826bd81d3542a419d6".to_string(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = fox_file(&temp_dir);
+        assert!(verify_size(path.clone(), 43).await.unwrap());
+        assert!(!verify_size(path.clone(), 42).await.unwrap());
+        assert!(verify_size("/nonexistent/file.bin".to_string(), 0)
+            .await
+            .is_err());
+    }
+}
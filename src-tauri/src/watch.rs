@@ -0,0 +1,240 @@
+use crate::algorithms::Algorithm;
+use crate::operations::Operations;
+use crate::{calculate_file_hash, HashResult};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Manager, Runtime, State};
+
+/// How long writes must pause before the file is hashed again, so a
+/// download in progress isn't rehashed on every chunk.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How often an idle watch checks whether it has been cancelled.
+const CANCEL_POLL: Duration = Duration::from_millis(250);
+
+type EventReceiver = Receiver<notify::Result<Event>>;
+
+/// Watches the directory containing `path` rather than the file itself:
+/// editors and downloaders often save by renaming a new file over the old
+/// one, after which a watch on the original file never fires again.
+fn start_watching(path: &str) -> notify::Result<(RecommendedWatcher, EventReceiver)> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    let dir = Path::new(path)
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    Ok((watcher, receiver))
+}
+
+/// Whether `event` creates, writes or renames onto the file called `name`.
+fn touches(event: &Event, name: Option<&OsString>) -> bool {
+    matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == name.map(OsString::as_os_str))
+}
+
+/// Hashes `path` now and again after each burst of changes, handing every
+/// result to `emit` until it returns false or `cancel` is set. Hashes that
+/// fail, e.g. while an editor swaps the file, are skipped.
+fn run_watch(
+    path: &str,
+    _watcher: RecommendedWatcher,
+    events: EventReceiver,
+    cancel: &AtomicBool,
+    mut emit: impl FnMut(HashResult) -> bool,
+) {
+    let name = Path::new(path).file_name().map(|name| name.to_os_string());
+    let hash = || calculate_file_hash(path, &Algorithm::DEFAULT).ok();
+    if let Some(result) = hash() {
+        if !emit(result) {
+            return;
+        }
+    }
+    // Wake up regularly even without events, so a cancelled watch on an
+    // idle file still ends and releases its watcher
+    while !cancel.load(Ordering::SeqCst) {
+        let event = match events.recv_timeout(CANCEL_POLL) {
+            Ok(Ok(event)) => event,
+            Ok(Err(_)) | Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return,
+        };
+        if !touches(&event, name.as_ref()) {
+            continue;
+        }
+        while events.recv_timeout(DEBOUNCE).is_ok() {}
+        if cancel.load(Ordering::SeqCst) {
+            return;
+        }
+        if let Some(result) = hash() {
+            if !emit(result) {
+                return;
+            }
+        }
+    }
+}
+
+/// Source of ids for [`watch_file`], unique for the life of the process.
+static NEXT_WATCH_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Sends the file's hash on `channel` straight away and again whenever it
+/// changes, including when another file is renamed over it.
+///
+/// Returns an id for [`unwatch_file`]; the watch is tracked in
+/// [`Operations`] like a running hash, so `cancel_all` and app exit stop it
+/// too. It also stops once the channel can no longer deliver.
+#[tauri::command]
+pub async fn watch_file<R: Runtime>(
+    path: String,
+    channel: Channel<HashResult>,
+    app: AppHandle<R>,
+) -> Result<String, String> {
+    if !Path::new(&path).is_file() {
+        return Err(format!("{}: not a file", path));
+    }
+    let (watcher, events) = start_watching(&path).map_err(|e| e.to_string())?;
+    let id = format!("watch-{}", NEXT_WATCH_ID.fetch_add(1, Ordering::Relaxed));
+    let (registered_tx, registered) = mpsc::channel();
+    let op_id = id.clone();
+    thread::spawn(move || {
+        let operations = app.state::<Operations>();
+        let guard = operations.register(&op_id);
+        let _ = registered_tx.send(());
+        run_watch(&path, watcher, events, guard.flag(), |result| {
+            channel.send(result).is_ok()
+        })
+    });
+    // Only hand out the id once it can be cancelled
+    let _ = registered.recv();
+    Ok(id)
+}
+
+/// Stops the watch started by [`watch_file`] with `id`. Returns false if it
+/// isn't running.
+#[tauri::command]
+pub fn unwatch_file(id: String, operations: State<'_, Operations>) -> bool {
+    operations.cancel(&id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Watches `path` on a background thread, returning its results and a
+    /// receiver that fires once the watch has ended.
+    fn spawn_watch(path: &str, cancel: Arc<AtomicBool>) -> (Receiver<HashResult>, Receiver<()>) {
+        let (watcher, events) = start_watching(path).unwrap();
+        let (results_tx, results) = mpsc::channel();
+        let (done_tx, done) = mpsc::channel();
+        let watched = path.to_string();
+        thread::spawn(move || {
+            run_watch(&watched, watcher, events, &cancel, |result| {
+                results_tx.send(result).is_ok()
+            });
+            let _ = done_tx.send(());
+        });
+        (results, done)
+    }
+
+    #[test]
+    fn test_modification_emits_new_result() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("download.part");
+        fs::write(&file, b"first").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let (results, _done) = spawn_watch(&path, Arc::default());
+
+        let timeout = Duration::from_secs(10);
+        let first = results.recv_timeout(timeout).unwrap();
+        assert_eq!(first.file_size, 5);
+
+        fs::write(&file, b"second version").unwrap();
+        let second = results.recv_timeout(timeout).unwrap();
+        assert_eq!(second.file_size, 14);
+        assert_ne!(first.sha256, second.sha256);
+    }
+
+    #[test]
+    fn test_rename_over_emits_new_result() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("notes.txt");
+        fs::write(&file, b"first").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let (results, _done) = spawn_watch(&path, Arc::default());
+        let timeout = Duration::from_secs(10);
+        assert_eq!(results.recv_timeout(timeout).unwrap().file_size, 5);
+
+        // An editor's atomic save: write a sibling, then rename it over
+        let staged = temp_dir.path().join(".notes.txt.swp");
+        fs::write(&staged, b"saved atomically").unwrap();
+        fs::rename(&staged, &file).unwrap();
+        assert_eq!(results.recv_timeout(timeout).unwrap().file_size, 16);
+
+        // Later saves are still seen
+        fs::write(&file, b"edited").unwrap();
+        assert_eq!(results.recv_timeout(timeout).unwrap().file_size, 6);
+    }
+
+    #[test]
+    fn test_cancel_stops_idle_watch() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("idle.bin");
+        fs::write(&file, b"idle").unwrap();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let (results, done) = spawn_watch(&file.to_string_lossy(), cancel.clone());
+        results.recv_timeout(Duration::from_secs(10)).unwrap();
+        cancel.store(true, Ordering::SeqCst);
+
+        done.recv_timeout(Duration::from_secs(10)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unwatch_file() {
+        let app = tauri::test::mock_app();
+        app.manage(Operations::default());
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("watched.txt");
+        fs::write(&file, b"watched").unwrap();
+
+        let id = watch_file(
+            file.to_string_lossy().to_string(),
+            Channel::new(|_| Ok(())),
+            app.handle().clone(),
+        )
+        .await
+        .unwrap();
+        assert!(unwatch_file(id, app.state()));
+    }
+
+    #[tokio::test]
+    async fn test_watch_file_rejects_missing_path() {
+        let app = tauri::test::mock_app();
+        app.manage(Operations::default());
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("gone.txt");
+        let channel = Channel::new(|_| Ok(()));
+
+        assert!(watch_file(
+            missing.to_string_lossy().to_string(),
+            channel,
+            app.handle().clone()
+        )
+        .await
+        .is_err());
+    }
+}
@@ -118,6 +118,39 @@ mod checksum_tests {
         assert_eq!(result, "07e547d9586f6a73f73fbac0435ed76951218fb7d0c8d788a309d785436bbb642e93a252a954f23912547d1e8a3b5ed6e1bfd7097821233fa0538f3db854fee6");
     }
 
+    #[test]
+    fn test_known_content_sha3() {
+        use sha3::{Digest, Sha3_256, Sha3_512};
+
+        let empty_256 = format!("{:x}", Sha3_256::digest(b""));
+        let empty_512 = format!("{:x}", Sha3_512::digest(b""));
+        assert_eq!(
+            empty_256,
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+        assert_eq!(empty_512, "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26");
+
+        let content = b"The quick brown fox jumps over the lazy dog";
+        let (_temp_dir, _) = create_test_file(content);
+
+        let mut hasher = Sha3_256::new();
+        hasher.update(content);
+        let result = format!("{:x}", hasher.finalize());
+
+        // Known SHA3-256 hash for this content
+        assert_eq!(
+            result,
+            "69070dda01975c8c120c3aada1b282394e7f032fa9cf32f4cb2259a0897dfc04"
+        );
+
+        let mut hasher = Sha3_512::new();
+        hasher.update(content);
+        let result = format!("{:x}", hasher.finalize());
+
+        // Known SHA3-512 hash for this content
+        assert_eq!(result, "01dedd5de4ef14642445ba5f5b97c15e47b9ad931326e4b0727cd94cefc44fff23f07bf543139939b49128caf436dc1bdee54fcb24023a08d9403f9b4bf0d450");
+    }
+
     #[test]
     fn test_small_file() {
         let content = b"Hello, World!";
@@ -284,11 +317,17 @@ mod checksum_tests {
         sha512_hasher.update(content);
         let sha512_result = format!("{:x}", sha512_hasher.finalize());
 
+        use sha3::{Sha3_256, Sha3_512};
+        let sha3_256_result = format!("{:x}", Sha3_256::digest(content));
+        let sha3_512_result = format!("{:x}", Sha3_512::digest(content));
+
         // Verify correct hash lengths
         assert_eq!(md5_result.len(), 32); // 128 bits = 32 hex chars
         assert_eq!(sha1_result.len(), 40); // 160 bits = 40 hex chars
         assert_eq!(sha256_result.len(), 64); // 256 bits = 64 hex chars
         assert_eq!(sha512_result.len(), 128); // 512 bits = 128 hex chars
+        assert_eq!(sha3_256_result.len(), 64);
+        assert_eq!(sha3_512_result.len(), 128);
     }
 
     #[test]
@@ -461,3 +500,100 @@ mod checksum_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod cli_tests {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn test_stdin_sha256() {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ChecksumCheck"))
+            .args(["--algo", "sha256", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"The quick brown fox jumps over the lazy dog")
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap().trim(),
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+    }
+
+    #[test]
+    fn test_json_output() {
+        let (_temp_dir, file_path) =
+            super::create_test_file(b"The quick brown fox jumps over the lazy dog");
+        let output = Command::new(env!("CARGO_BIN_EXE_ChecksumCheck"))
+            .args(["--json", &file_path])
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(
+            result["sha256"],
+            "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592"
+        );
+        assert_eq!(result["md5"], "9e107d9d372bb6826bd81d3542a419d6");
+        assert_eq!(result["file_size"], 43);
+    }
+
+    #[test]
+    fn test_json_missing_file_fails() {
+        let output = Command::new(env!("CARGO_BIN_EXE_ChecksumCheck"))
+            .args(["--json", "/nonexistent/file.iso"])
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+    }
+
+    #[test]
+    fn test_stdin_large_input() {
+        // Several MiB so the digest is built across many chunked reads
+        let content = vec![0xABu8; 5 * 1024 * 1024];
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ChecksumCheck"))
+            .args(["--algo", "md5", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = std::thread::spawn(move || stdin.write_all(&content).unwrap());
+        let output = child.wait_with_output().unwrap();
+        writer.join().unwrap();
+
+        use md5::Md5;
+        use sha2::Digest;
+        let mut hasher = Md5::new();
+        hasher.update(vec![0xABu8; 5 * 1024 * 1024]);
+        let expected = format!("{:x}", hasher.finalize());
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), expected);
+    }
+
+    #[test]
+    fn test_unknown_algorithm_fails() {
+        let output = Command::new(env!("CARGO_BIN_EXE_ChecksumCheck"))
+            .args(["--algo", "crc99", "-"])
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+
+        assert!(!output.status.success());
+    }
+}
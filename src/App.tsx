@@ -17,8 +17,11 @@ function formatFileSize(bytes: number): string {
   return `${size.toFixed(2)} ${units[unitIndex]}`;
 }
 
-function formatDate(timestamp: string): string {
-  return new Date(parseInt(timestamp) * 1000).toLocaleString();
+function formatDate(timestamp: number | null): string {
+  if (timestamp === null) {
+    return "Unknown";
+  }
+  return new Date(timestamp * 1000).toLocaleString();
 }
 
 function App() {
@@ -29,8 +32,8 @@ function App() {
     sha256: string;
     sha512: string;
     file_size: number;
-    modified: string;
-    created: string;
+    modified: number | null;
+    created: number | null;
   } | null>(null);
   const [theme, setTheme] = useState("dark");
   const [isDragging, setIsDragging] = useState(false);
@@ -60,7 +63,7 @@ function App() {
             ) {
               const filePath = event.payload.paths[0];
               setSelectedFile(filePath);
-              invoke("calculate_checksum", { path: filePath })
+              invoke("calculate_checksum", { request: { path: filePath } })
                 .then((result) => setHashResults(result as any))
                 .catch((error) => {
                   console.error("Error calculating checksum:", error);
@@ -108,7 +111,9 @@ function App() {
       setSelectedFile(filePath);
 
       try {
-        const result = await invoke("calculate_checksum", { path: filePath });
+        const result = await invoke("calculate_checksum", {
+          request: { path: filePath },
+        });
         setHashResults(result as any);
       } catch (error) {
         console.error("Error calculating checksum:", error);